@@ -1,7 +1,12 @@
-use anyhow::bail;
+use std::{io, time::Duration};
+
+use anyhow::{bail, Context};
 use clap::{Parser, Subcommand};
 
-use crate::generator::{self};
+use crate::generator::{
+    self, ByteaFormat, Framework, Indent, IntervalFormat, LineEnding, NameCollision,
+};
+use autostruct::database::Kind;
 
 #[derive(Parser)]
 #[command(name = "autostruct")]
@@ -22,6 +27,14 @@ pub enum Commands {
                       to the specified output directory. Please run this command with --help to see what arguments can be used to configure it"
     )]
     Generate(GenerateArgs),
+    #[command(
+        about = "List the tables, enums and composite types that structs would be generated for",
+        long_about = "List the tables, enums and composite types that structs would be generated for.\n\n\
+                      This command accepts the same arguments as generate and prints each database object along with \
+                      the name of the Rust type it would be generated as, including any database types that would be \
+                      mapped to an unknown custom type. No files are written"
+    )]
+    List(GenerateArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -30,35 +43,513 @@ pub struct GenerateArgs {
     #[arg(short, long, default_value = "./output")]
     pub output: String,
 
-    /// Sets the connection string to the database. It will use the DATABASE_URL environment variable if set
+    /// Prints the generated files to stdout instead of writing them to the output directory
+    #[arg(long, default_value_t = false)]
+    pub stdout: bool,
+
+    /// Sets the suffix of the generated files, e.g. `.gen.rs`. The mod.rs declares the modules with a #[path] attribute when it isn't `.rs`
+    #[arg(long, default_value = generator::DEFAULT_FILE_SUFFIX)]
+    pub file_suffix: String,
+
+    /// Generates a prelude module that re-exports every generated type, so they can be brought into scope with `use models::prelude::*;`
+    #[arg(long, default_value_t = false)]
+    pub prelude: bool,
+
+    /// Only rewrites the files whose code changed since the last incremental run, based on the fingerprints stored in the output directory, so regenerating a large schema doesn't churn unchanged files
+    #[arg(long, default_value_t = false)]
+    pub incremental: bool,
+
+    /// Prints the database type of every column and the Rust type it is mapped to, e.g. `users.id: int4 -> i32`, instead of writing any files. Columns mapped to a custom type are marked (custom)
+    #[arg(long, default_value_t = false)]
+    pub dry_run_print_mapping: bool,
+
+    /// Declares a SCHEMA_HASH constant in mod.rs with the hash of the schema the code was generated from, which an application can compare with the hash of the live database to detect that the code is stale
+    #[arg(long, default_value_t = false)]
+    pub schema_hash: bool,
+
+    /// Starts the generated mod.rs with a //! module doc comment of the provided text, e.g. 'Generated by autostruct from {schema} at {timestamp}'. {schema} is replaced with the name of the schema and {timestamp} with the time of generation in UTC
+    #[arg(long)]
+    pub module_doc: Option<String>,
+
+    /// Omits the "// Generated with autostruct" comment that the generated files start with, e.g. for tools that further process the files
+    #[arg(long, default_value_t = false)]
+    pub no_banner: bool,
+
+    /// Generates several outputs from a single introspection of the database, e.g. sqlx structs for a backend and serde only structs for a shared crate. Each line of the file holds the generate arguments of one output, such as `-o ./shared --derive Serialize`, and the database url of the command is used unless the line sets its own. Blank lines and lines starting with # are skipped, and the other arguments of the command are ignored
+    #[arg(long)]
+    pub profiles: Option<String>,
+
+    /// Aborts with an error if connecting, generating and writing the files takes longer than the provided number of seconds, e.g. to keep a CI pipeline from hanging
+    #[arg(long)]
+    pub max_runtime: Option<u64>,
+
+    /// Retries connecting this many times with a growing delay while the database is not reachable yet, e.g. while its container is starting. Retrying stops at --max-runtime
+    #[arg(long, default_value_t = 0)]
+    pub connection_retries: u32,
+
+    /// Sets the connection string to the database. It will use the DATABASE_URL environment variable if set. Can be used multiple times to generate the code of each database into a submodule of the output directory
     #[arg(short, long, env = "DATABASE_URL")]
-    pub database_url: Option<String>,
+    pub database_url: Vec<String>,
+
+    /// Sets the kind of database to connect to instead of inferring it from the scheme of the connection string, e.g. when a proxy uses a postgres:// URL for another engine
+    #[arg(long, value_enum)]
+    pub dialect: Option<Kind>,
 
     /// Creates struct names in the singular variant of the table name
     #[arg(long, default_value_t = false)]
     pub singular: bool,
 
+    /// Creates file and module names in the singular variant of the table name, independently of --singular
+    #[arg(long, default_value_t = false)]
+    pub singular_modules: bool,
+
+    /// Sets the database framework the generated code integrates with
+    #[arg(long, value_enum, default_value_t = Framework::None)]
+    pub framework: Framework,
+
+    /// Derives the provided comma separated traits for the generated types. Traits that a type's fields don't implement (e.g. Eq for floats) are skipped for that type, and fields serde can't handle are marked #[serde(skip)]
+    #[arg(long, value_delimiter = ',')]
+    pub derive: Vec<String>,
+
+    /// Places the provided attribute verbatim above every generated struct and enum, e.g. '#[cfg_attr(test, derive(Default))]'. Can be used multiple times
+    #[arg(long)]
+    pub struct_attr: Vec<String>,
+
+    /// Derives Copy and Clone for enums and for structs whose fields are all Copy
+    #[arg(long, default_value_t = false)]
+    pub derive_copy_when_possible: bool,
+
+    /// Marks enums #[non_exhaustive]. Without a framework, enums deriving serde's Deserialize also get a #[serde(other)] Unknown variant, so new labels don't break deserialization. With --framework sqlx use --enum-unknown-variant, since sqlx can't decode new labels into that variant
+    #[arg(long, default_value_t = false)]
+    pub non_exhaustive_enums: bool,
+
+    /// Adds an Unknown(String) variant to enums, which labels missing from the enum are decoded into instead of failing. With --framework sqlx the sqlx::Type implementation is generated by hand
+    #[arg(long, default_value_t = false)]
+    pub enum_unknown_variant: bool,
+
+    /// Implements TryFrom<&str> for enums, converting their database labels into variants, e.g. Mood::try_from("happy")
+    #[arg(long, default_value_t = false)]
+    pub enum_try_from: bool,
+
+    /// Generates an ID newtype (e.g. UserId) for tables with a single column primary key, which the primary key and foreign keys referencing it are represented as
+    #[arg(long, default_value_t = false)]
+    pub typed_ids: bool,
+
+    /// Generates the typed IDs of --typed-ids as aliases of a generic Id<T> that is phantom typed by the struct of their table (e.g. pub type UserId = Id<Users>;), so they share one implementation but can't be mixed up. Requires --typed-ids
+    #[arg(long, default_value_t = false)]
+    pub phantom_ids: bool,
+
+    /// Represents citext columns as a generated CiText newtype whose equality is case insensitive, instead of a String
+    #[arg(long, default_value_t = false)]
+    pub citext_newtype: bool,
+
+    /// Marks fields of nullable columns #[serde(default, skip_serializing_if = "Option::is_none")], so None is left out instead of serialized as null. Requires serde's Serialize or Deserialize in --derive
+    #[arg(long, default_value_t = false)]
+    pub serde_null_as_missing: bool,
+
+    /// Represents interval columns as a generated Interval struct of their months, days and microseconds
+    #[arg(long, default_value_t = false)]
+    pub interval_struct: bool,
+
+    /// Documents each field with the database type it was generated from, e.g. /// db type: varchar(255)
+    #[arg(long, default_value_t = false)]
+    pub annotate_types: bool,
+
+    /// Documents fields whose column has a default with its expression, e.g. /// default: now()
+    #[arg(long, default_value_t = false)]
+    pub annotate_defaults: bool,
+
+    /// Generates only the enums and composite types, skipping the structs of tables, e.g. to publish a crate of types shared by several services
+    #[arg(long, default_value_t = false)]
+    pub types_only: bool,
+
     /// Exclude table names from being generated into structs
     #[arg(long)]
     pub exclude: Vec<String>,
+
+    /// Exclude a column in the form of table.column from being generated into its struct. Can be used multiple times
+    #[arg(long)]
+    pub exclude_column: Vec<String>,
+
+    /// Generates structs for the partitions of partitioned tables in addition to the partitioned table itself
+    #[arg(long, default_value_t = false)]
+    pub include_partitions: bool,
+
+    /// Generates code for tables and types that are owned by extensions, such as PostGIS. These are skipped by default
+    #[arg(long, default_value_t = false)]
+    pub include_extensions: bool,
+
+    /// Generates an enum from the rows of a lookup table instead of a struct, which columns referencing the table are represented as. Can be used multiple times
+    #[arg(long)]
+    pub enum_from_table: Vec<String>,
+
+    /// Sets the search_path of the database session, e.g. "app, public", so unqualified type names resolve the same way they do for the application
+    #[arg(long)]
+    pub search_path: Option<String>,
+
+    /// Generates only the columns the provided role can select, and marks the columns it can't update as read only
+    #[arg(long)]
+    pub role: Option<String>,
+
+    /// Sets the database (catalog) to introspect instead of the one named in the connection string
+    #[arg(long)]
+    pub catalog: Option<String>,
+
+    /// Introspects tables from pg_catalog instead of information_schema, whose views may only show the tables and constraints the role owns on managed databases
+    #[arg(long, default_value_t = false)]
+    pub pg_catalog: bool,
+
+    /// Maps a column to a Rust type in the form of table.column=path::to::Type, e.g. a jsonb column to a known struct. Can be used multiple times
+    #[arg(long, value_parser = parse_mapping)]
+    pub map_column: Vec<(String, String)>,
+
+    /// Represents an integer column as a bitflags type with the provided flags in the form of table.column=FLAG:value,..., e.g. users.permissions=READ:1,WRITE:2. Can be used multiple times
+    #[arg(long, value_parser = parse_bitflags)]
+    pub bitflags: Vec<(String, Vec<(String, u64)>)>,
+
+    /// Implements Display for the struct of a table by displaying one of its columns in the form of table=column, e.g. users=email. Can be used multiple times
+    #[arg(long, value_parser = parse_mapping)]
+    pub display_field: Vec<(String, String)>,
+
+    /// Represents a column in the form of table.column as an Option, regardless of its nullability in the database. Can be used multiple times
+    #[arg(long)]
+    pub force_nullable: Vec<String>,
+
+    /// Never represents a column in the form of table.column as an Option, regardless of its nullability in the database. Can be used multiple times
+    #[arg(long)]
+    pub force_not_null: Vec<String>,
+
+    /// Represents the elements of array columns as Option, since arrays can contain NULL elements regardless of the nullability of their column, e.g. Option<Vec<Option<i32>>> for a nullable int[]
+    #[arg(long, default_value_t = false)]
+    pub nullable_array_elements: bool,
+
+    /// Represents money columns as rust_decimal::Decimal instead of PgMoney. Queries must cast these columns with ::numeric. Without a framework money columns are always represented as Decimal
+    #[arg(long, default_value_t = false)]
+    pub money_as_decimal: bool,
+
+    /// Sets the Rust type interval columns are represented as. `duration` maps to chrono::Duration, which sqlx can't decode, so it can't be used with --framework sqlx. Defaults to `pg` with --framework sqlx and to `duration` with --framework none, which keeps an explicit `pg`
+    #[arg(long, value_enum)]
+    pub interval_as: Option<IntervalFormat>,
+
+    /// Represents numeric columns with a scale of 0 and a precision of at most 18 as i64. Queries must cast these columns with ::int8
+    #[arg(long, default_value_t = false)]
+    pub numeric_as_integer: bool,
+
+    /// Sets the Rust type bytea columns are represented as. `bytes` maps to bytes::Bytes to avoid copying the data
+    #[arg(long, value_enum, default_value_t = ByteaFormat::Vec)]
+    pub bytea_as: ByteaFormat,
+
+    /// Generates a `new` function for each table struct that takes all non optional fields and sets optional fields to None
+    #[arg(long, default_value_t = false)]
+    pub constructors: bool,
+
+    /// Represents bit(1) columns as bool and char(1) columns as char. Queries must cast these columns, e.g. with ::int::bool
+    #[arg(long, default_value_t = false)]
+    pub compact_flags: bool,
+
+    /// Represents bit(n) columns whose length is a multiple of 8, such as bit(256) hashes, as [u8; n / 8] byte arrays. Queries must select these columns as bytes, e.g. with substring(varbit_send(hash) from 5)
+    #[arg(long, default_value_t = false)]
+    pub fixed_binary: bool,
+
+    /// Annotates optional fields with #[sqlx(default)] so rows selecting a subset of the columns can still be decoded. Requires --framework sqlx
+    #[arg(long, default_value_t = false)]
+    pub sqlx_default_options: bool,
+
+    /// Implements sqlx::FromRow by hand instead of deriving it, so the column to field mapping can be edited after generation. Requires --framework sqlx
+    #[arg(long, default_value_t = false)]
+    pub manual_from_row: bool,
+
+    /// Experimental: generates functions fetching the rows of each table with the sqlx::query_as! macro, which verifies the queries against the database at compile time. Building the generated code requires DATABASE_URL or the offline data of `cargo sqlx prepare`. Requires --framework sqlx
+    #[arg(long, default_value_t = false)]
+    pub query_macros: bool,
+
+    /// Generates a columns() function for each table struct returning the names of its columns in the order of the fields, for building queries without hardcoding the names
+    #[arg(long, default_value_t = false)]
+    pub column_list: bool,
+
+    /// Generates a New<Struct> insert struct for each table with an insert function, and update and delete functions keyed on the primary key, which return the rows with the values the database generated. Requires --framework sqlx
+    #[arg(long, default_value_t = false)]
+    pub crud: bool,
+
+    /// Sets which type is renamed when a table and an enum or composite type share a name. `type` suffixes the enum with Enum or the composite with Composite, `table` suffixes the table struct with Row
+    #[arg(long, value_enum, default_value_t = NameCollision::Type)]
+    pub rename_on_collision: NameCollision,
+
+    /// Sets the indentation of the generated code, either `tabs` or the number of spaces per level
+    #[arg(long, value_parser = parse_indent, default_value = "4")]
+    pub indent: Indent,
+
+    /// Sets the line endings of the generated files
+    #[arg(long, value_enum, default_value_t = LineEnding::Lf)]
+    pub line_ending: LineEnding,
 }
 
-impl TryInto<generator::Arguments> for GenerateArgs {
+impl GenerateArgs {
+    /// Returns the arguments of each profile in the file passed to --profiles, or `None` when no file is passed
+    pub fn profiles(&self) -> anyhow::Result<Option<Vec<generator::Arguments>>> {
+        let Some(path) = &self.profiles else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read the profiles file {path}"))?;
+
+        let mut profiles = vec![];
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let arguments = split_arguments(line)
+                .map_err(|err| anyhow::anyhow!("line {} of {path}: {err}", index + 1))?;
+            let mut profile = GenerateArgs::try_parse_from(
+                std::iter::once("generate".to_string()).chain(arguments),
+            )
+            .with_context(|| format!("failed to parse line {} of {path}", index + 1))?;
+            if profile.profiles.is_some() {
+                bail!(
+                    "line {} of {path}: a profile can not pass --profiles",
+                    index + 1
+                );
+            }
+            if profile.database_url.is_empty() {
+                profile.database_url = self.database_url.clone();
+            }
+            let mut databases: Vec<generator::Arguments> = profile.try_into()?;
+            if databases.len() > 1 {
+                bail!(
+                    "line {} of {path}: a profile can only introspect a single database",
+                    index + 1
+                );
+            }
+            profiles.append(&mut databases);
+        }
+        if profiles.is_empty() {
+            bail!("the profiles file {path} does not contain any profiles");
+        }
+        Ok(Some(profiles))
+    }
+}
+
+/// Converts the arguments into the arguments of each database url, which only differ in their connection string
+impl TryInto<Vec<generator::Arguments>> for GenerateArgs {
     type Error = anyhow::Error;
 
-    fn try_into(self) -> Result<generator::Arguments, Self::Error> {
-        let conn_str = match self.database_url {
-            Some(url) => url,
-            None => bail!("no database url provided - please set it via command line arguments or with the DATABASE_URL environment variable"),
+    fn try_into(mut self) -> Result<Vec<generator::Arguments>, Self::Error> {
+        let database_urls = std::mem::take(&mut self.database_url);
+        let Some(conn_str) = database_urls.first().cloned() else {
+            bail!("no database url provided - please set it via command line arguments or with the DATABASE_URL environment variable");
         };
 
+        if self.profiles.is_some() {
+            bail!("--profiles can only be used with generate");
+        }
+
+        if !self.file_suffix.ends_with(".rs") || self.file_suffix.contains(['/', '\\']) {
+            bail!(
+                "--file-suffix must end with .rs and can not contain a path separator but got `{}`",
+                self.file_suffix
+            );
+        }
+
+        // the ignore file of the working directory adds to the tables and columns excluded on the command line
+        let (ignored_tables, ignored_columns) =
+            match std::fs::read_to_string(generator::IGNORE_FILE) {
+                Ok(content) => generator::parse_ignore_file(&content),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Default::default(),
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("failed to read {}", generator::IGNORE_FILE))
+                }
+            };
+
+        if self.interval_as == Some(IntervalFormat::Duration) && self.framework == Framework::Sqlx {
+            bail!("--interval-as duration can not be used with --framework sqlx, since sqlx can't decode intervals into chrono::Duration");
+        }
+
+        if self.sqlx_default_options && self.framework != Framework::Sqlx {
+            bail!("--sqlx-default-options can only be used with --framework sqlx");
+        }
+
+        if self.manual_from_row && self.framework != Framework::Sqlx {
+            bail!("--manual-from-row can only be used with --framework sqlx");
+        }
+
+        if self.query_macros && self.framework != Framework::Sqlx {
+            bail!("--query-macros can only be used with --framework sqlx");
+        }
+
+        if self.crud && self.framework != Framework::Sqlx {
+            bail!("--crud can only be used with --framework sqlx");
+        }
+
+        if self.phantom_ids && !self.typed_ids {
+            bail!("--phantom-ids can only be used with --typed-ids");
+        }
+
+        if self.interval_struct && self.interval_as == Some(IntervalFormat::Duration) {
+            bail!("--interval-struct can not be used with --interval-as duration");
+        }
+
+        if let Some(column) = self
+            .force_nullable
+            .iter()
+            .find(|column| self.force_not_null.contains(column))
+        {
+            bail!(
+                "column {column} can not be passed to both --force-nullable and --force-not-null"
+            );
+        }
+
         let args = generator::Arguments {
             target_dir: self.output,
+            stdout: self.stdout,
+            file_suffix: self.file_suffix,
+            prelude: self.prelude,
+            incremental: self.incremental,
+            print_mapping: self.dry_run_print_mapping,
+            schema_hash: self.schema_hash,
+            module_doc: self.module_doc,
+            no_banner: self.no_banner,
+            max_runtime: self.max_runtime.map(Duration::from_secs),
+            connection_retries: self.connection_retries,
             connection_string: conn_str,
+            dialect: self.dialect,
             singular_table_names: self.singular,
-            exclude_tables: self.exclude,
+            singular_module_names: self.singular_modules,
+            framework: self.framework,
+            derives: self
+                .derive
+                .iter()
+                .map(|d| d.trim().to_string())
+                .filter(|d| !d.is_empty())
+                .collect(),
+            struct_attributes: self
+                .struct_attr
+                .iter()
+                .map(|a| a.trim().to_string())
+                .filter(|a| !a.is_empty())
+                .collect(),
+            derive_copy_when_possible: self.derive_copy_when_possible,
+            non_exhaustive_enums: self.non_exhaustive_enums,
+            enum_unknown_variant: self.enum_unknown_variant,
+            enum_try_from: self.enum_try_from,
+            typed_ids: self.typed_ids,
+            phantom_ids: self.phantom_ids,
+            citext_newtype: self.citext_newtype,
+            serde_null_as_missing: self.serde_null_as_missing,
+            interval_struct: self.interval_struct,
+            annotate_types: self.annotate_types,
+            annotate_defaults: self.annotate_defaults,
+            types_only: self.types_only,
+            exclude_tables: self.exclude.into_iter().chain(ignored_tables).collect(),
+            exclude_columns: self
+                .exclude_column
+                .into_iter()
+                .chain(ignored_columns)
+                .collect(),
+            include_partitions: self.include_partitions,
+            include_extensions: self.include_extensions,
+            enum_tables: self.enum_from_table,
+            search_path: self.search_path,
+            role: self.role,
+            catalog: self.catalog,
+            pg_catalog: self.pg_catalog,
+            column_types: self.map_column.into_iter().collect(),
+            bitflags: self.bitflags.into_iter().collect(),
+            display_fields: self.display_field.into_iter().collect(),
+            nullable_columns: self.force_nullable.into_iter().collect(),
+            not_null_columns: self.force_not_null.into_iter().collect(),
+            nullable_array_elements: self.nullable_array_elements,
+            money_as_decimal: self.money_as_decimal,
+            interval_format: self.interval_as,
+            numeric_as_integer: self.numeric_as_integer,
+            bytea_format: self.bytea_as,
+            constructors: self.constructors,
+            compact_flags: self.compact_flags,
+            fixed_binary: self.fixed_binary,
+            sqlx_default_options: self.sqlx_default_options,
+            manual_from_row: self.manual_from_row,
+            query_macros: self.query_macros,
+            column_list: self.column_list,
+            crud: self.crud,
+            rename_on_collision: self.rename_on_collision,
+            indent: self.indent,
+            line_ending: self.line_ending,
         };
 
-        Ok(args)
+        Ok(database_urls
+            .into_iter()
+            .map(|url| generator::Arguments {
+                connection_string: url,
+                ..args.clone()
+            })
+            .collect())
+    }
+}
+
+/// Splits a line of a profiles file into its arguments at whitespace, keeping whitespace that is enclosed in single or
+/// double quotes, e.g. `--module-doc 'Shared models'`
+fn split_arguments(line: &str) -> Result<Vec<String>, String> {
+    let mut arguments = vec![];
+    let mut argument: Option<String> = None;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), c) => argument.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                argument.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => arguments.extend(argument.take()),
+            (None, c) => argument.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(open) = quote {
+        return Err(format!("the quote {open} is not closed"));
+    }
+    arguments.extend(argument);
+    Ok(arguments)
+}
+
+/// Parses a mapping in the form of `key=value`
+fn parse_mapping(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, mapped)) if !key.is_empty() && !mapped.is_empty() => {
+            Ok((key.to_string(), mapped.to_string()))
+        }
+        _ => Err(format!(
+            "expected a mapping in the form of key=value but got `{value}`"
+        )),
+    }
+}
+
+/// Parses the flags of a column in the form of `table.column=FLAG:value,...`
+fn parse_bitflags(value: &str) -> Result<(String, Vec<(String, u64)>), String> {
+    let (column, flags) = parse_mapping(value)?;
+    let flags = flags
+        .split(',')
+        .map(|flag| match flag.split_once(':') {
+            Some((name, bits)) if !name.is_empty() => bits
+                .parse()
+                .map(|bits| (name.to_string(), bits))
+                .map_err(|_| {
+                    format!("expected the value of flag `{name}` to be a number but got `{bits}`")
+                }),
+            _ => Err(format!(
+                "expected a flag in the form of FLAG:value but got `{flag}`"
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((column, flags))
+}
+
+/// Parses an indentation that is either `tabs` or a number of spaces
+fn parse_indent(value: &str) -> Result<Indent, String> {
+    match value {
+        "tab" | "tabs" => Ok(Indent::Tabs),
+        width => width.parse().map(Indent::Spaces).map_err(|_| {
+            format!("expected `tabs` or a number of spaces for the indentation but got `{value}`")
+        }),
     }
 }
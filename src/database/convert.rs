@@ -12,27 +12,51 @@ use super::{
 };
 
 /**
-The `TableConverter` trait defines a common interface for converting types into a vector of TableInfo
+The `TableConverter` trait defines a common interface for converting types into a vector of `Table`
+
+# Arguments
+- `excluded_columns`: columns in the form of `table.column` that are omitted from the converted tables
 */
 pub trait TableConverter {
-    fn to_tables(self) -> Vec<Table>;
+    fn to_tables(self, excluded_columns: &[String]) -> Vec<Table>;
 }
 
 impl TableConverter for Vec<TableColumn> {
-    fn to_tables(self) -> Vec<Table> {
-        let tables: HashMap<String, Vec<Column>> = HashMap::new();
-        self.into_iter()
-            .fold(tables, |mut acc, mut column| {
+    fn to_tables(self, excluded_columns: &[String]) -> Vec<Table> {
+        let mut tables: Vec<Table> = self
+            .into_iter()
+            .filter(|column| {
+                let qualified_name = format!("{}.{}", column.table_name, column.column_name);
+                if !excluded_columns.contains(&qualified_name) {
+                    return true;
+                }
+                if column.is_primary_key {
+                    eprintln!("warning: excluded column {qualified_name} is part of the primary key of its table");
+                }
+                false
+            })
+            .fold(HashMap::<String, Table>::new(), |mut acc, mut column| {
                 let table_name = mem::take(&mut column.table_name);
-                acc.entry(table_name).or_default().push(column.into());
+                let table_comment = column.table_comment.take();
+                let is_foreign = column.is_foreign;
+                let is_materialized_view = column.is_materialized_view;
+                acc.entry(table_name.clone())
+                    .or_insert_with(|| Table {
+                        name: table_name,
+                        comment: table_comment,
+                        columns: Vec::new(),
+                        is_foreign,
+                        is_materialized_view,
+                    })
+                    .columns
+                    .push(column.into());
                 acc
             })
-            .into_iter()
-            .map(|t| Table {
-                name: t.0,
-                columns: t.1,
-            })
-            .collect()
+            .into_values()
+            .collect();
+        // the tables are grouped in a map, so they are sorted to keep their order the same between runs
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        tables
     }
 }
 
@@ -42,13 +66,22 @@ impl From<TableColumn> for Column {
         Column {
             name: val.column_name,
             udt_name: val.udt_name,
+            udt_schema: val.udt_schema,
             data_type: val.data_type,
+            numeric_precision: val.numeric_precision,
+            numeric_scale: val.numeric_scale,
+            character_maximum_length: val.character_maximum_length,
             is_nullable: val.is_nullable,
+            column_default: val.column_default,
+            is_generated: val.is_generated,
             is_unique: val.is_unique,
             is_primary_key: val.is_primary_key,
+            foreign_key_schema: val.foreign_key_schema,
             foreign_key_table: val.foreign_key_table,
             foreign_key_id: val.foreign_key_id,
             table_schema: val.table_schema,
+            is_writable: val.is_writable,
+            comment: val.comment,
         }
     }
 }
@@ -59,26 +92,34 @@ pub trait EnumConverter {
 
 impl EnumConverter for Vec<EnumType> {
     fn to_enums(self) -> Vec<Enum> {
-        let enumerations: HashMap<String, Vec<EnumValue>> = HashMap::new();
-        self.into_iter()
+        let enumerations: HashMap<(String, String), Vec<EnumValue>> = HashMap::new();
+        let mut enums = self
+            .into_iter()
             .fold(enumerations, |mut acc, mut e| {
-                let enum_name = mem::take(&mut e.name);
-                acc.entry(enum_name).or_default().push(EnumValue {
+                let key = (mem::take(&mut e.schema_name), mem::take(&mut e.name));
+                acc.entry(key).or_default().push(EnumValue {
                     name: e.value,
                     order: e.sort_order,
+                    discriminant: None,
                 });
                 acc
             })
             .into_iter()
             .map(|mut e| {
                 // ensure enums are sorted
-                e.1.sort_by(|a, b| a.order.total_cmp(&b.order));
+                e.1.sort_by(EnumValue::cmp_order);
+                let (schema, name) = e.0;
                 Enum {
-                    name: e.0,
+                    schema,
+                    name,
                     values: e.1,
+                    backing_type: None,
                 }
             })
-            .collect()
+            .collect::<Vec<Enum>>();
+        // the enums are grouped in a map, so they are sorted to keep their order the same between runs
+        enums.sort_by(|a, b| (&a.schema, &a.name).cmp(&(&b.schema, &b.name)));
+        enums
     }
 }
 
@@ -88,20 +129,25 @@ pub trait CompositeTypeConverter {
 
 impl CompositeTypeConverter for Vec<raw_schema::CompositeType> {
     fn to_composite_types(self) -> Vec<schema::CompositeType> {
-        let composites: HashMap<String, Vec<Attribute>> = HashMap::new();
+        let composites: HashMap<(String, String), Vec<Attribute>> = HashMap::new();
         self.into_iter()
             .fold(composites, |mut acc, mut composite| {
-                let c_name = mem::take(&mut composite.name);
-                acc.entry(c_name).or_default().push(Attribute {
+                let key = (
+                    mem::take(&mut composite.schema_name),
+                    mem::take(&mut composite.name),
+                );
+                acc.entry(key).or_default().push(Attribute {
                     name: composite.attribute_name,
                     data_type: composite.data_type,
+                    data_type_schema: composite.data_type_schema,
                 });
                 acc
             })
             .into_iter()
-            .map(|c| schema::CompositeType {
-                name: c.0,
-                attributes: c.1,
+            .map(|((schema, name), attributes)| schema::CompositeType {
+                schema,
+                name,
+                attributes,
             })
             .collect()
     }
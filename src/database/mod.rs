@@ -1,7 +1,8 @@
 /*!
 The `database` module provides a common interface for interacting with various database systems to retrieve table column information.
-It defines the `Database` trait that must be implemented by all supported database systems and provides the `TableColumn` struct to
-represent column information.
+It defines the `InfoProvider` trait that must be implemented by all supported database systems and the `DatabaseSchema` it
+returns, whose `Table` and `Column` structs represent the tables and their columns. Backends that read the schema with raw
+queries map their rows (see `raw_schema`) into this model with the converters of the `convert` module.
 
 Supported database systems include:
 - PostgreSQL
@@ -9,38 +10,71 @@ Supported database systems include:
 - MSSQL
 - SQLite
 
-Each supported database has its own module implementing the `Database` trait.
+Each supported database has its own module implementing the `InfoProvider` trait, which is only compiled in when the
+Cargo feature of the same name (`postgres`, `mysql`, `mssql` or `sqlite`) is enabled.
 */
 
+#[cfg(feature = "mssql")]
 pub mod mssql;
+#[cfg(feature = "mysql")]
 pub mod mysql;
+#[cfg(feature = "postgres")]
 pub mod postgres;
+#[cfg(feature = "sqlite")]
 pub mod sqlite;
 
+#[cfg(feature = "postgres")]
 mod convert;
+#[cfg(feature = "postgres")]
 mod raw_schema;
 
 mod schema;
-pub use schema::{CompositeType, Enum, InfoProvider, Table};
+pub use schema::{
+    Attribute, Blocking, Column, CompositeType, DatabaseSchema, Enum, EnumValue, InfoProvider,
+    SyncInfoProvider, Table,
+};
 
 use anyhow::{bail, Error};
+use clap::ValueEnum;
 
 /**
-The Kind of databases that autostruct supports
+The Kind of databases that autostruct supports. It is inferred from the scheme of the connection string, unless it is
+set explicitly as the dialect
 */
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Kind {
     Postgres,
+    #[value(name = "mysql")]
     MySql,
+    #[value(name = "mssql")]
     MsSql,
     Sqlite,
 }
 
+impl Kind {
+    /// The name of the Cargo feature the provider of this kind is compiled in with
+    pub fn feature(&self) -> &'static str {
+        match self {
+            Kind::Postgres => "postgres",
+            Kind::MySql => "mysql",
+            Kind::MsSql => "mssql",
+            Kind::Sqlite => "sqlite",
+        }
+    }
+}
+
 impl TryFrom<&str> for Kind {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let db = if value.starts_with("postgres://") {
+        let db = if value.starts_with("postgres://") || value.starts_with("postgresql://") {
             Self::Postgres
+        } else if value.starts_with("mysql://") {
+            Self::MySql
+        } else if value.starts_with("mssql://") || value.starts_with("sqlserver://") {
+            Self::MsSql
+        } else if value.starts_with("sqlite:") {
+            Self::Sqlite
         } else {
             bail!("failed to infer database kind from provided connection string")
         };
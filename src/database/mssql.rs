@@ -0,0 +1,39 @@
+/*!
+Maps SQL Server column types to Rust types.
+*/
+
+use crate::rust::{self, Type};
+
+/**
+Maps a SQL Server type name (as reported by `information_schema.columns.data_type`) to its Rust type. This does
+not require a connection, which allows the mapping to be used when generating code from a schema that was not read
+from a live database.
+
+Some types have mappings that can't be inferred from their name:
+- `uniqueidentifier` is a GUID and maps to `uuid::Uuid`
+- `datetimeoffset` stores an offset alongside the timestamp and maps to `chrono::DateTime<chrono::FixedOffset>`
+- `rowversion` and its deprecated synonym `timestamp` are not temporal types, but 8 byte row version counters
+*/
+pub fn type_name_from(data_type: &str) -> rust::Type {
+    match data_type.to_lowercase().as_str() {
+        "bit" => Type::Bool("bool"),
+        // tinyint is unsigned in SQL Server, so it is widened to fit values above 127
+        "tinyint" | "smallint" => Type::I16("i16"),
+        "int" => Type::I32("i32"),
+        "bigint" => Type::I64("i64"),
+        "decimal" | "numeric" | "money" | "smallmoney" => Type::Decimal("rust_decimal::Decimal"),
+        "real" => Type::F32("f32"),
+        "float" => Type::F64("f64"),
+        "uniqueidentifier" => Type::Uuid("uuid::Uuid"),
+        "date" => Type::Date("chrono::NaiveDate"),
+        "time" => Type::Time("chrono::NaiveTime"),
+        "datetime" | "datetime2" | "smalldatetime" => Type::Timestamp("chrono::NaiveDateTime"),
+        "datetimeoffset" => Type::TimestampWithTz("chrono::DateTime<chrono::FixedOffset>"),
+        "char" | "varchar" | "text" | "nchar" | "nvarchar" | "ntext" | "sysname" => {
+            Type::String("String")
+        }
+        "xml" => Type::Xml("String"),
+        "binary" | "varbinary" | "image" | "rowversion" | "timestamp" => Type::ByteArray("Vec<u8>"),
+        other => Type::Custom(other.to_string()),
+    }
+}
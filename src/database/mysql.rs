@@ -1 +1,74 @@
+/*!
+Maps MySQL column types to Rust types.
 
+MySQL reports the full column type in `information_schema.columns.column_type`, including the display width and
+attributes such as `unsigned` (e.g. `int(10) unsigned zerofill`), which all need to be considered for the mapping.
+*/
+
+use crate::rust::{self, Type};
+
+/**
+Maps a MySQL column type to its Rust type. This does not require a connection, which allows the mapping to be used
+when generating code from a schema that was not read from a live database.
+
+Unsigned integers are mapped to the unsigned type of the same size (e.g. `int unsigned` to `u32`), since sqlx only
+decodes columns with the `UNSIGNED` flag into unsigned types, and the upper half of their range doesn't fit into the
+signed type of the same size anyway.
+*/
+pub fn type_name_from(column_type: &str) -> rust::Type {
+    let column_type = column_type.to_lowercase();
+    let mut words = column_type.split_whitespace();
+    let declared_type = words.next().unwrap_or_default();
+    let is_unsigned = words.any(|attribute| attribute == "unsigned");
+    let (base_type, display_width) = declared_type
+        .split_once('(')
+        .map(|(base_type, width)| (base_type, width.trim_end_matches(')')))
+        .unwrap_or((declared_type, ""));
+
+    match base_type {
+        // `boolean` is an alias for `tinyint(1)`
+        "tinyint" if display_width == "1" && !is_unsigned => Type::Bool("bool"),
+        "bool" | "boolean" => Type::Bool("bool"),
+        t if INTEGER_TYPES.contains(&t) => map_integer_type(t, is_unsigned),
+        "decimal" | "dec" | "numeric" | "fixed" => Type::Decimal("rust_decimal::Decimal"),
+        "float" => Type::F32("f32"),
+        "double" | "real" => Type::F64("f64"),
+        "date" => Type::Date("chrono::NaiveDate"),
+        "time" => Type::Time("chrono::NaiveTime"),
+        "datetime" => Type::Timestamp("chrono::NaiveDateTime"),
+        // timestamps are stored in UTC and converted to the session time zone
+        "timestamp" => Type::TimestampWithTz("chrono::DateTime<chrono::Utc>"),
+        "char" | "varchar" | "tinytext" | "text" | "mediumtext" | "longtext" | "enum" | "set" => {
+            Type::String("String")
+        }
+        "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" | "bit" => {
+            Type::ByteArray("Vec<u8>")
+        }
+        "json" => Type::Json("serde_json::Value"),
+        other => Type::Custom(other.to_string()),
+    }
+}
+
+const INTEGER_TYPES: &[&str] = &[
+    "tinyint",
+    "smallint",
+    "mediumint",
+    "int",
+    "integer",
+    "bigint",
+];
+
+fn map_integer_type(typ: &str, is_unsigned: bool) -> rust::Type {
+    match (typ, is_unsigned) {
+        ("tinyint", false) => Type::I8("i8"),
+        ("tinyint", true) => Type::U8("u8"),
+        ("smallint", false) => Type::I16("i16"),
+        ("smallint", true) => Type::U16("u16"),
+        // mediumint is a 24 bit integer, which is decoded into the 32 bit type of its signedness
+        ("mediumint" | "int" | "integer", false) => Type::I32("i32"),
+        ("mediumint" | "int" | "integer", true) => Type::U32("u32"),
+        ("bigint", false) => Type::I64("i64"),
+        ("bigint", true) => Type::U64("u64"),
+        _ => unreachable!("invalid integer type"),
+    }
+}
@@ -1,28 +1,70 @@
 /*!
-The `postgres` module provides an implementation of the `TableInfoProvider` trait for PostgreSQL databases.
+The `postgres` module provides an implementation of the `InfoProvider` trait for PostgreSQL databases.
 */
 
 use crate::{
     database::InfoProvider,
     rust::{self, Type},
 };
-use anyhow::{Context, Error};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Error};
 use async_trait::async_trait;
-use sqlx::{PgPool, Pool, Postgres};
+use cruet::Inflector;
+use sqlx::{postgres::PgConnectOptions, PgConnection, PgPool, Pool, Postgres};
 
 use super::{
     convert::{CompositeTypeConverter, EnumConverter, TableConverter},
     raw_schema::{self, TableColumn},
-    schema::{self, DatabaseSchema, Enum},
+    schema::{self, DatabaseSchema, Enum, EnumValue},
     Table,
 };
 
+/// A function that decides the Rust type of a PostgreSQL type before the built-in mapping is consulted, returning
+/// `None` to fall back to it.
+pub type TypeResolver = Box<dyn Fn(&str) -> Option<rust::Type> + Send + Sync>;
+
+/// The delay before the first retry of a failed connection attempt, which doubles with each further retry
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// The longest delay between two connection attempts
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// The SQLSTATE PostgreSQL rejects connections with while the server is starting up
+const CANNOT_CONNECT_NOW: &str = "57P03";
+
 // A builder for configuring and creating a `Database` connection.
 pub struct Builder {
     /// The schema to use for the database connection.
     schema: Option<String>,
     /// A list of tables to exclude from the database connection.
     excluded_tables: Vec<String>,
+    /// A list of columns in the form of `table.column` to exclude from the database connection.
+    excluded_columns: Vec<String>,
+    /// Whether the partitions of partitioned tables should be included alongside their parent.
+    include_partitions: bool,
+    /// Whether objects installed by extensions should be included.
+    include_extensions: bool,
+    /// A list of lookup tables whose rows are generated as an enum instead of a struct.
+    enum_tables: Vec<String>,
+    /// The `search_path` set for each connection, which unqualified type names are resolved against.
+    search_path: Option<String>,
+    /// A function consulted before the built-in type mapping.
+    type_resolver: Option<TypeResolver>,
+    /// The role whose column privileges decide which columns are included and writable.
+    role: Option<String>,
+    /// The database to introspect instead of the one named in the connection string.
+    catalog: Option<String>,
+    /// The number of times a failed connection attempt is retried.
+    connection_retries: u32,
+    /// Whether tables are introspected from `pg_catalog` instead of `information_schema`.
+    pg_catalog: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Builder {
@@ -31,6 +73,16 @@ impl Builder {
         Self {
             schema: None,
             excluded_tables: Vec::new(),
+            excluded_columns: Vec::new(),
+            include_partitions: false,
+            include_extensions: false,
+            enum_tables: Vec::new(),
+            search_path: None,
+            type_resolver: None,
+            role: None,
+            catalog: None,
+            connection_retries: 0,
+            pg_catalog: false,
         }
     }
 
@@ -48,6 +100,161 @@ impl Builder {
         self
     }
 
+    /// Excludes the specified columns from the database connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - A vector of column names in the form of `table.column` to exclude.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified columns excluded.
+    pub fn exclude_columns(mut self, columns: Vec<String>) -> Self {
+        self.excluded_columns = columns;
+        self
+    }
+
+    /// Sets whether the partitions of partitioned tables should be included. By default only the
+    /// partitioned parent table is included.
+    ///
+    /// # Arguments
+    ///
+    /// * `include` - Whether to include partitions.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified partition handling.
+    pub fn include_partitions(mut self, include: bool) -> Self {
+        self.include_partitions = include;
+        self
+    }
+
+    /// Sets whether tables and types that are owned by an extension (e.g. the `spatial_ref_sys` table of PostGIS)
+    /// should be included. By default they are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `include` - Whether to include extension objects.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified extension handling.
+    pub fn include_extensions(mut self, include: bool) -> Self {
+        self.include_extensions = include;
+        self
+    }
+
+    /// Generates an enum from the rows of the specified lookup tables instead of a struct. Each row becomes a
+    /// variant named after its first text column and keyed by its integer primary key, and columns referencing
+    /// the table via a foreign key are represented as the enum.
+    ///
+    /// # Arguments
+    ///
+    /// * `tables` - A vector of lookup table names.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified lookup tables.
+    pub fn enum_tables(mut self, tables: Vec<String>) -> Self {
+        self.enum_tables = tables;
+        self
+    }
+
+    /// Sets the `search_path` of each connection (e.g. `app, public`), so that unqualified type names resolve the
+    /// same way they do for the application. By default the `search_path` of the connecting role is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_path` - A comma separated list of schemas, or `None` to keep the role's default.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified search path.
+    pub fn search_path(mut self, search_path: Option<String>) -> Self {
+        self.search_path = search_path;
+        self
+    }
+
+    /// Registers a function that is consulted for every PostgreSQL type before the built-in mapping, which allows
+    /// library consumers to decide the Rust type of any type (e.g. a domain or an extension type). Arrays are
+    /// passed with their leading underscore first, and their element type is resolved if that returns `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - A function returning the Rust type of a PostgreSQL type, or `None` to use the built-in mapping.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified type resolver.
+    pub fn with_type_resolver(mut self, resolver: TypeResolver) -> Self {
+        self.type_resolver = Some(resolver);
+        self
+    }
+
+    /// Sets the role whose column privileges are consulted. Columns the role can't select are omitted, and
+    /// columns it can't update are marked as not writable. By default every column is included and writable.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The name of the role, or `None` to ignore privileges.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified role.
+    pub fn role(mut self, role: Option<String>) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Sets the database (catalog) to introspect instead of the one named in the connection string. Since
+    /// PostgreSQL only exposes the catalog a session is connected to, the connection is made to this database.
+    ///
+    /// # Arguments
+    ///
+    /// * `catalog` - The name of the database, or `None` to use the one from the connection string.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified catalog.
+    pub fn catalog(mut self, catalog: Option<String>) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
+    /// Sets how many times connecting is retried when the database is not reachable yet, e.g. while its container
+    /// is starting. The delay between attempts starts at 250 milliseconds and doubles up to 5 seconds. By default
+    /// the first failed attempt is returned as an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `retries` - The number of retries after the first attempt.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified number of retries.
+    pub fn connection_retries(mut self, retries: u32) -> Self {
+        self.connection_retries = retries;
+        self
+    }
+
+    /// Sets whether tables are introspected from the system catalogs (`pg_class`, `pg_attribute` and
+    /// `pg_constraint`) instead of `information_schema`. Managed databases may restrict the views of
+    /// `information_schema` to objects the role owns, which hides tables, nullability and constraints the role can
+    /// still query. The catalogs include every table the role has a privilege on. By default `information_schema`
+    /// is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to introspect the system catalogs.
+    ///
+    /// # Returns
+    ///
+    /// A `Builder` instance with the specified introspection source.
+    pub fn pg_catalog(mut self, enabled: bool) -> Self {
+        self.pg_catalog = enabled;
+        self
+    }
+
     /// Sets the schema to use for the database connection.
     ///
     /// # Arguments
@@ -72,13 +279,31 @@ impl Builder {
     ///
     /// A `Result` containing the `Database` instance or an error.
     pub async fn connect(self, connection_string: &str) -> Result<impl InfoProvider, Error> {
-        let pool = PgPool::connect(connection_string)
+        let mut options: PgConnectOptions = connection_string
+            .parse()
+            .context("failed to parse postgresql connection string")?;
+        if let Some(search_path) = &self.search_path {
+            // startup options apply to every connection of the pool, including reconnects
+            options = options.options([("search_path", escape_option(search_path))]);
+        }
+        if let Some(catalog) = &self.catalog {
+            options = options.database(catalog);
+        }
+
+        let pool = connect_with_retries(options, self.connection_retries)
             .await
             .context("failed to connect to postgresql database")?;
 
         let db = Database {
             pool,
             excluded_tables: self.excluded_tables,
+            excluded_columns: self.excluded_columns,
+            include_partitions: self.include_partitions,
+            include_extensions: self.include_extensions,
+            enum_tables: self.enum_tables,
+            type_resolver: self.type_resolver,
+            role: self.role,
+            pg_catalog: self.pg_catalog,
             schema: self.schema.map_or(String::from("public"), |v| v),
         };
 
@@ -86,18 +311,58 @@ impl Builder {
     }
 }
 
+/// Connects to the database, retrying attempts that failed because the database is not reachable yet with a growing
+/// delay. Other failures, such as a wrong password, are returned right away
+async fn connect_with_retries(
+    options: PgConnectOptions,
+    retries: u32,
+) -> Result<PgPool, sqlx::Error> {
+    let mut delay = INITIAL_RETRY_DELAY;
+    let mut attempt = 0;
+    loop {
+        match PgPool::connect_with(options.clone()).await {
+            Ok(pool) => return Ok(pool),
+            Err(err) if attempt < retries && is_transient(&err) => {
+                attempt += 1;
+                eprintln!(
+                    "warning: failed to connect to postgresql database, retrying in {delay:?} ({attempt}/{retries}): {err}"
+                );
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RETRY_DELAY);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Returns whether a connection error may go away by itself, such as a refused connection or a server starting up
+fn is_transient(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(err) => err.code().as_deref() == Some(CANNOT_CONNECT_NOW),
+        _ => false,
+    }
+}
+
 /**
     Represents a connection to a PostgreSQL database with various options used to meaningully
-    implement the `TableInfoProvider` trait
+    implement the `InfoProvider` trait
 */
 pub struct Database {
     pool: Pool<Postgres>,
     schema: String,
     excluded_tables: Vec<String>,
+    excluded_columns: Vec<String>,
+    include_partitions: bool,
+    include_extensions: bool,
+    enum_tables: Vec<String>,
+    type_resolver: Option<TypeResolver>,
+    role: Option<String>,
+    pg_catalog: bool,
 }
 
 impl Database {
-    async fn get_enums(&self) -> Result<Vec<Enum>, Error> {
+    async fn get_enums(&self, connection: &mut PgConnection) -> Result<Vec<Enum>, Error> {
         let query = "
         SELECT
             n.nspname AS schema_name,
@@ -111,26 +376,50 @@ impl Database {
         JOIN
             pg_enum e ON t.oid = e.enumtypid
         WHERE
-            n.nspname = $1
+            (n.nspname = $1
+            OR t.oid IN (
+                -- types of other schemas used by the columns of the generated tables and composite types
+                SELECT rt.oid
+                FROM pg_attribute ra
+                JOIN pg_class rc ON ra.attrelid = rc.oid
+                JOIN pg_namespace rn ON rc.relnamespace = rn.oid
+                JOIN pg_type rt ON ra.atttypid IN (rt.oid, rt.typarray)
+                WHERE rn.nspname = $1
+                    AND rc.relkind IN ('r', 'p', 'v', 'm', 'f', 'c')
+                    AND ra.attnum > 0
+                    AND NOT ra.attisdropped
+            ))
+            AND ($2 OR NOT EXISTS (
+                SELECT 1
+                FROM pg_depend d
+                WHERE d.classid = 'pg_type'::regclass
+                    AND d.objid = t.oid
+                    AND d.deptype = 'e'
+            ))
         ORDER BY
             schema_name, t.typname, e.enumsortorder;";
 
         let enums = sqlx::query_as::<_, raw_schema::EnumType>(query)
             .bind(&self.schema)
-            .fetch_all(&self.pool)
+            .bind(self.include_extensions)
+            .fetch_all(&mut *connection)
             .await?
             .to_enums();
 
         Ok(enums)
     }
 
-    async fn get_composite_types(&self) -> Result<Vec<schema::CompositeType>, Error> {
+    async fn get_composite_types(
+        &self,
+        connection: &mut PgConnection,
+    ) -> Result<Vec<schema::CompositeType>, Error> {
         let query = "
         SELECT
             n.nspname AS schema_name,
             t.typname AS name,
             a.attname AS attribute_name,
             bt.typname AS data_type,
+            bn.nspname AS data_type_schema,
             a.attnum AS attribute_position
         FROM
             pg_type t
@@ -142,17 +431,39 @@ impl Database {
             pg_attribute a ON c.oid = a.attrelid
         JOIN
             pg_type bt ON a.atttypid = bt.oid
+        JOIN
+            pg_namespace bn ON bt.typnamespace = bn.oid
         WHERE
             t.typtype = 'c'
             AND c.relkind = 'c'
             AND a.attnum > 0
-            AND n.nspname = $1
+            AND (n.nspname = $1
+            OR t.oid IN (
+                -- types of other schemas used by the columns of the generated tables and composite types
+                SELECT rt.oid
+                FROM pg_attribute ra
+                JOIN pg_class rc ON ra.attrelid = rc.oid
+                JOIN pg_namespace rn ON rc.relnamespace = rn.oid
+                JOIN pg_type rt ON ra.atttypid IN (rt.oid, rt.typarray)
+                WHERE rn.nspname = $1
+                    AND rc.relkind IN ('r', 'p', 'v', 'm', 'f', 'c')
+                    AND ra.attnum > 0
+                    AND NOT ra.attisdropped
+            ))
+            AND ($2 OR NOT EXISTS (
+                SELECT 1
+                FROM pg_depend d
+                WHERE d.classid = 'pg_type'::regclass
+                    AND d.objid = t.oid
+                    AND d.deptype = 'e'
+            ))
         ORDER BY
             schema_name, t.typname, a.attnum;";
 
         let composite_types = sqlx::query_as::<_, raw_schema::CompositeType>(query)
             .bind(&self.schema)
-            .fetch_all(&self.pool)
+            .bind(self.include_extensions)
+            .fetch_all(&mut *connection)
             .await?
             .to_composite_types();
 
@@ -160,27 +471,97 @@ impl Database {
     }
 
     /**
-    Retrieves a list of columns for all tables in the PostgreSQL database.
+    Retrieves a list of columns for all tables in the PostgreSQL database. Only ordinary, partitioned and foreign
+    tables, views and materialized views are included, so sequences, indexes, toast tables and composite types
+    never produce a struct.
 
     # Returns
-    - A `Result` containing a vector of `TableInfo` structs or an error.
+    - A `Result` containing a vector of `Table` structs or an error.
     */
-    async fn get_table_info(&self) -> Result<Vec<Table>, Error> {
-        let excluded_tables = self.excluded_tables.join(",");
+    async fn get_table_info(&self, connection: &mut PgConnection) -> Result<Vec<Table>, Error> {
         let query = "
     SELECT
         c.table_name,
         c.column_name,
         c.udt_name,
+        c.udt_schema,
         c.data_type,
+        c.numeric_precision::int4 AS numeric_precision,
+        c.numeric_scale::int4 AS numeric_scale,
+        c.character_maximum_length::int4 AS character_maximum_length,
         c.is_nullable = 'YES' AS is_nullable,
+        c.column_default,
+        c.is_generated,
         COALESCE(tc.constraint_type = 'UNIQUE', false) AS is_unique,
         COALESCE(tc.constraint_type = 'PRIMARY KEY', false) AS is_primary_key,
+        kcu2.table_schema AS foreign_key_schema,
         kcu2.table_name AS foreign_key_table,
         kcu2.column_name AS foreign_key_id,
-        c.table_schema
+        c.table_schema,
+        $5::text IS NULL
+            OR has_column_privilege($5, format('%I.%I', c.table_schema, c.table_name), c.column_name, 'UPDATE')
+            AS is_writable,
+        col_description(format('%I.%I', c.table_schema, c.table_name)::regclass, c.ordinal_position::int4) AS comment,
+        obj_description(format('%I.%I', c.table_schema, c.table_name)::regclass, 'pg_class') AS table_comment,
+        EXISTS (
+            SELECT 1
+            FROM pg_catalog.pg_class pc
+            JOIN pg_catalog.pg_namespace pn ON pc.relnamespace = pn.oid
+            WHERE pn.nspname = c.table_schema
+                AND pc.relname = c.table_name
+                AND pc.relkind = 'f'
+        ) AS is_foreign,
+        EXISTS (
+            SELECT 1
+            FROM pg_catalog.pg_class pc
+            JOIN pg_catalog.pg_namespace pn ON pc.relnamespace = pn.oid
+            WHERE pn.nspname = c.table_schema
+                AND pc.relname = c.table_name
+                AND pc.relkind = 'm'
+        ) AS is_materialized_view
     FROM
-        information_schema.columns c
+        (
+            SELECT
+                table_schema::text,
+                table_name::text,
+                column_name::text,
+                udt_name::text,
+                udt_schema::text,
+                data_type::text,
+                numeric_precision::int4,
+                numeric_scale::int4,
+                character_maximum_length::int4,
+                is_nullable::text,
+                column_default::text,
+                is_identity = 'YES' OR is_generated = 'ALWAYS' AS is_generated,
+                ordinal_position::int4
+            FROM information_schema.columns
+            UNION ALL
+            -- materialized views are missing from the information schema, so their columns are read from the catalog
+            SELECT
+                pn.nspname::text,
+                pc.relname::text,
+                a.attname::text,
+                bt.typname::text,
+                btn.nspname::text,
+                format_type(bt.oid, NULL),
+                information_schema._pg_numeric_precision(bt.oid, information_schema._pg_truetypmod(a.*, t.*)),
+                information_schema._pg_numeric_scale(bt.oid, information_schema._pg_truetypmod(a.*, t.*)),
+                information_schema._pg_char_max_length(bt.oid, information_schema._pg_truetypmod(a.*, t.*)),
+                CASE WHEN a.attnotnull THEN 'NO' ELSE 'YES' END,
+                NULL,
+                false,
+                a.attnum::int4
+            FROM pg_catalog.pg_attribute a
+            JOIN pg_catalog.pg_class pc ON a.attrelid = pc.oid
+            JOIN pg_catalog.pg_namespace pn ON pc.relnamespace = pn.oid
+            JOIN pg_catalog.pg_type t ON a.atttypid = t.oid
+            JOIN pg_catalog.pg_type bt ON bt.oid = information_schema._pg_truetypid(a.*, t.*)
+            JOIN pg_catalog.pg_namespace btn ON bt.typnamespace = btn.oid
+            WHERE pc.relkind = 'm'
+                AND a.attnum > 0
+                AND NOT a.attisdropped
+        ) c
         LEFT JOIN information_schema.key_column_usage kcu
             ON c.table_name = kcu.table_name
             AND c.column_name = kcu.column_name
@@ -190,51 +571,337 @@ impl Database {
             AND kcu.table_schema = tc.table_schema
         LEFT JOIN information_schema.referential_constraints rc
             ON kcu.constraint_name = rc.constraint_name
+            AND kcu.constraint_schema = rc.constraint_schema
         LEFT JOIN information_schema.key_column_usage kcu2
             ON rc.unique_constraint_name = kcu2.constraint_name
-            AND kcu2.ordinal_position = kcu.ordinal_position
+            AND kcu2.ordinal_position = kcu.position_in_unique_constraint
             AND kcu2.table_schema = rc.unique_constraint_schema
     WHERE
         c.table_schema = $1
-        AND c.table_name NOT IN ($2)
-
+        AND c.table_name <> ALL ($2)
+        AND EXISTS (
+            SELECT 1
+            FROM pg_catalog.pg_class pc
+            JOIN pg_catalog.pg_namespace pn ON pc.relnamespace = pn.oid
+            WHERE pn.nspname = c.table_schema
+                AND pc.relname = c.table_name
+                AND pc.relkind IN ('r', 'p', 'v', 'm', 'f')
+        )
+        AND (
+            $3
+            OR NOT EXISTS (
+                SELECT 1
+                FROM pg_catalog.pg_class pc
+                JOIN pg_catalog.pg_namespace pn ON pc.relnamespace = pn.oid
+                WHERE pn.nspname = c.table_schema
+                    AND pc.relname = c.table_name
+                    AND pc.relispartition
+            )
+        )
+        AND (
+            $4
+            OR NOT EXISTS (
+                SELECT 1
+                FROM pg_catalog.pg_class pc
+                JOIN pg_catalog.pg_namespace pn ON pc.relnamespace = pn.oid
+                JOIN pg_catalog.pg_depend d
+                    ON d.classid = 'pg_class'::regclass
+                    AND d.objid = pc.oid
+                    AND d.deptype = 'e'
+                WHERE pn.nspname = c.table_schema
+                    AND pc.relname = c.table_name
+            )
+        )
+        AND (
+            $5::text IS NULL
+            OR has_column_privilege($5, format('%I.%I', c.table_schema, c.table_name), c.column_name, 'SELECT')
+        )
     ORDER BY
         c.table_name,
         c.ordinal_position;";
 
         let tables = sqlx::query_as::<_, TableColumn>(query)
             .bind(&self.schema)
-            .bind(excluded_tables)
-            .fetch_all(&self.pool)
+            .bind(&self.excluded_tables)
+            .bind(self.include_partitions)
+            .bind(self.include_extensions)
+            .bind(&self.role)
+            .fetch_all(&mut *connection)
+            .await?
+            .to_tables(&self.excluded_columns);
+
+        Ok(tables)
+    }
+
+    /**
+    Retrieves the same columns as `get_table_info` from the system catalogs instead of `information_schema`, whose
+    views only show the constraints of tables the role owns or has a privilege other than `SELECT` on. Tables are
+    included when the role has any privilege on one of their columns, and the nullability of domain columns includes
+    the `NOT NULL` of the domain.
+
+    # Returns
+    - A `Result` containing a vector of `Table` structs or an error.
+    */
+    async fn get_catalog_table_info(
+        &self,
+        connection: &mut PgConnection,
+    ) -> Result<Vec<Table>, Error> {
+        let query = "
+    SELECT
+        pc.relname::text AS table_name,
+        a.attname::text AS column_name,
+        bt.typname::text AS udt_name,
+        btn.nspname::text AS udt_schema,
+        format_type(bt.oid, NULL) AS data_type,
+        CASE
+            WHEN bt.typname = 'numeric' AND typ.typmod <> -1 THEN ((typ.typmod - 4) >> 16) & 65535
+            WHEN bt.typname = 'int2' THEN 16
+            WHEN bt.typname = 'int4' THEN 32
+            WHEN bt.typname = 'int8' THEN 64
+            WHEN bt.typname = 'float4' THEN 24
+            WHEN bt.typname = 'float8' THEN 53
+        END AS numeric_precision,
+        CASE
+            WHEN bt.typname = 'numeric' AND typ.typmod <> -1 THEN (typ.typmod - 4) & 65535
+            WHEN bt.typname IN ('int2', 'int4', 'int8') THEN 0
+        END AS numeric_scale,
+        CASE
+            WHEN bt.typname IN ('bpchar', 'varchar') AND typ.typmod <> -1 THEN typ.typmod - 4
+            WHEN bt.typname IN ('bit', 'varbit') AND typ.typmod <> -1 THEN typ.typmod
+        END AS character_maximum_length,
+        NOT (a.attnotnull OR (t.typtype = 'd' AND t.typnotnull)) AS is_nullable,
+        CASE WHEN a.attgenerated = '' THEN pg_get_expr(ad.adbin, ad.adrelid) END AS column_default,
+        a.attidentity <> '' OR a.attgenerated <> '' AS is_generated,
+        EXISTS (
+            SELECT 1
+            FROM pg_catalog.pg_constraint con
+            WHERE con.conrelid = pc.oid
+                AND con.contype = 'u'
+                AND a.attnum = ANY (con.conkey)
+        ) AS is_unique,
+        EXISTS (
+            SELECT 1
+            FROM pg_catalog.pg_constraint con
+            WHERE con.conrelid = pc.oid
+                AND con.contype = 'p'
+                AND a.attnum = ANY (con.conkey)
+        ) AS is_primary_key,
+        fk.foreign_key_schema,
+        fk.foreign_key_table,
+        fk.foreign_key_id,
+        pn.nspname::text AS table_schema,
+        $5::text IS NULL OR has_column_privilege($5, pc.oid, a.attnum, 'UPDATE') AS is_writable,
+        col_description(pc.oid, a.attnum) AS comment,
+        obj_description(pc.oid, 'pg_class') AS table_comment,
+        pc.relkind = 'f' AS is_foreign,
+        pc.relkind = 'm' AS is_materialized_view
+    FROM pg_catalog.pg_attribute a
+    JOIN pg_catalog.pg_class pc ON a.attrelid = pc.oid
+    JOIN pg_catalog.pg_namespace pn ON pc.relnamespace = pn.oid
+    JOIN pg_catalog.pg_type t ON a.atttypid = t.oid
+    -- domains are represented as their base type, with the type modifier declared by the domain
+    CROSS JOIN LATERAL (
+        SELECT
+            CASE WHEN t.typtype = 'd' THEN t.typbasetype ELSE t.oid END AS oid,
+            CASE WHEN t.typtype = 'd' THEN t.typtypmod ELSE a.atttypmod END AS typmod
+    ) typ
+    JOIN pg_catalog.pg_type bt ON bt.oid = typ.oid
+    JOIN pg_catalog.pg_namespace btn ON bt.typnamespace = btn.oid
+    LEFT JOIN pg_catalog.pg_attrdef ad ON ad.adrelid = a.attrelid AND ad.adnum = a.attnum
+    LEFT JOIN LATERAL (
+        SELECT
+            fn.nspname::text AS foreign_key_schema,
+            fc.relname::text AS foreign_key_table,
+            fa.attname::text AS foreign_key_id
+        FROM pg_catalog.pg_constraint con
+        JOIN pg_catalog.pg_class fc ON con.confrelid = fc.oid
+        JOIN pg_catalog.pg_namespace fn ON fc.relnamespace = fn.oid
+        JOIN pg_catalog.pg_attribute fa
+            ON fa.attrelid = con.confrelid
+            AND fa.attnum = con.confkey[array_position(con.conkey, a.attnum)]
+        WHERE con.conrelid = pc.oid
+            AND con.contype = 'f'
+            AND a.attnum = ANY (con.conkey)
+        ORDER BY con.conname
+        LIMIT 1
+    ) fk ON true
+    WHERE
+        pn.nspname = $1
+        AND pc.relname::text <> ALL ($2)
+        AND pc.relkind IN ('r', 'p', 'v', 'm', 'f')
+        AND a.attnum > 0
+        AND NOT a.attisdropped
+        AND has_column_privilege(pc.oid, a.attnum, 'SELECT, INSERT, UPDATE, REFERENCES')
+        AND ($3 OR NOT pc.relispartition)
+        AND (
+            $4
+            OR NOT EXISTS (
+                SELECT 1
+                FROM pg_catalog.pg_depend d
+                WHERE d.classid = 'pg_class'::regclass
+                    AND d.objid = pc.oid
+                    AND d.deptype = 'e'
+            )
+        )
+        AND ($5::text IS NULL OR has_column_privilege($5, pc.oid, a.attnum, 'SELECT'))
+    ORDER BY
+        pc.relname,
+        a.attnum;";
+
+        let tables = sqlx::query_as::<_, TableColumn>(query)
+            .bind(&self.schema)
+            .bind(&self.excluded_tables)
+            .bind(self.include_partitions)
+            .bind(self.include_extensions)
+            .bind(&self.role)
+            .fetch_all(&mut *connection)
             .await?
-            .to_tables();
+            .to_tables(&self.excluded_columns);
 
         Ok(tables)
     }
+
+    /**
+    Generates an enum from the rows of a lookup table. The variants are named after the first text column of the
+    table and keyed by its integer primary key.
+
+    # Returns
+    - A `Result` containing the enum or an error if the table has no integer primary key or text column.
+    */
+    async fn get_enum_from_table(
+        &self,
+        connection: &mut PgConnection,
+        table: &Table,
+    ) -> Result<Enum, Error> {
+        let Some(key) = table
+            .columns
+            .iter()
+            .find(|c| c.is_primary_key && matches!(c.udt_name.as_str(), "int2" | "int4" | "int8"))
+        else {
+            bail!(
+                "lookup table {} has no integer primary key to generate an enum from",
+                table.name
+            );
+        };
+        let Some(label) = table
+            .columns
+            .iter()
+            .find(|c| matches!(c.udt_name.as_str(), "text" | "varchar" | "bpchar" | "citext"))
+        else {
+            bail!(
+                "lookup table {} has no text column to name the variants of its enum after",
+                table.name
+            );
+        };
+
+        let query = format!(
+            "SELECT {}::int8, {}::text FROM {}.{} ORDER BY 1",
+            quote_identifier(&key.name),
+            quote_identifier(&label.name),
+            quote_identifier(&self.schema),
+            quote_identifier(&table.name),
+        );
+        let rows = sqlx::query_as::<_, (i64, String)>(&query)
+            .fetch_all(&mut *connection)
+            .await
+            .with_context(|| {
+                format!("failed to retrieve the rows of lookup table {}", table.name)
+            })?;
+
+        let values = rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, (discriminant, name))| EnumValue {
+                name,
+                order: i as f32,
+                discriminant: Some(discriminant),
+            })
+            .collect();
+
+        Ok(Enum {
+            schema: self.schema.clone(),
+            name: table.name.to_singular(),
+            values,
+            backing_type: Some(key.udt_name.clone()),
+        })
+    }
+}
+
+// Escapes the value of a startup option, which PostgreSQL splits at whitespace unless it is
+// preceded by a backslash, so a value such as `app, public` isn't taken as two arguments
+fn escape_option(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || c.is_whitespace() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Quotes an identifier so it can be safely interpolated into a query
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
 }
 
 #[async_trait]
 impl InfoProvider for Database {
 
     fn type_name_from(&self, db_type: &str) -> rust::Type {
-        // Handle arrays first
-        if let Some(inner_type) = db_type.strip_prefix('_') {
-            return Type::Vector(Box::new(self.type_name_from(inner_type)));
-        }
-
-        match db_type {
-            t if NUMERIC_TYPES.contains(&t) => map_numeric_type(t),
-            t if TEMPORAL_TYPES.contains(&t) => map_temporal_type(t),
-            t if STRING_TYPES.contains(&t) => Type::String("String"),
-            t if BINARY_TYPES.contains(&t) => Type::ByteArray("Vec<u8>"),
-            t => map_specialized_type(t),
+        let Some(resolver) = &self.type_resolver else {
+            return type_name_from(db_type);
+        };
+        if let Some(rust_type) = resolver(db_type) {
+            return rust_type;
         }
+        map_array_type(db_type, &|element| {
+            resolver(element).unwrap_or_else(|| map_element_type(element))
+        })
     }
 
     async fn get_schema(&self) -> Result<DatabaseSchema, Error> {
-        let enumerations = self.get_enums().await?;
-        let composite_types = self.get_composite_types().await?;
-        let tables = self.get_table_info().await?;
+        // the queries share a snapshot of the database, so a migration running concurrently is
+        // either seen as a whole or not at all, instead of e.g. a table being retrieved without the
+        // enum of one of its columns
+        let mut transaction = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *transaction)
+            .await?;
+
+        let mut enumerations = self.get_enums(&mut transaction).await?;
+        let composite_types = self.get_composite_types(&mut transaction).await?;
+        let mut tables = match self.pg_catalog {
+            true => self.get_catalog_table_info(&mut transaction).await?,
+            false => self.get_table_info(&mut transaction).await?,
+        };
+
+        for enum_table in &self.enum_tables {
+            let Some(position) = tables.iter().position(|t| t.name == *enum_table) else {
+                bail!("lookup table {enum_table} does not exist");
+            };
+            let table = tables.remove(position);
+            let enumeration = self.get_enum_from_table(&mut transaction, &table).await?;
+            let primary_key = table
+                .columns
+                .iter()
+                .find(|c| c.is_primary_key)
+                .map(|c| c.name.as_str());
+            // columns referencing the primary key of the lookup table are represented as its enum.
+            // Columns referencing one of its unique columns, such as the label, keep their type
+            for column in tables.iter_mut().flat_map(|t| t.columns.iter_mut()) {
+                if column.foreign_key_schema.as_deref() == Some(self.schema.as_str())
+                    && column.foreign_key_table.as_deref() == Some(enum_table.as_str())
+                    && column.foreign_key_id.as_deref() == primary_key
+                {
+                    column.udt_name = enumeration.name.clone();
+                    column.udt_schema = enumeration.schema.clone();
+                }
+            }
+            enumerations.push(enumeration);
+        }
+        transaction.commit().await?;
+
         let schema = DatabaseSchema {
             enumerations,
             composite_types,
@@ -245,6 +912,37 @@ impl InfoProvider for Database {
 }
 
 
+/**
+Maps a PostgreSQL type name to its Rust type. This does not require a connection, which allows
+the mapping to be used when generating code from a schema that was not read from a live database
+*/
+pub fn type_name_from(db_type: &str) -> rust::Type {
+    map_array_type(db_type, &map_element_type)
+}
+
+/**
+Maps an array type by stripping each leading `_` into a Vec around its element type. This is the only
+place arrays are resolved, so nested arrays of enums and composite types map the same way for every caller
+*/
+fn map_array_type(db_type: &str, map_element: &dyn Fn(&str) -> rust::Type) -> rust::Type {
+    match db_type.strip_prefix('_') {
+        Some(inner_type) => Type::Vector(Box::new(map_array_type(inner_type, map_element))),
+        None => map_element(db_type),
+    }
+}
+
+// Maps a type that is not an array
+fn map_element_type(db_type: &str) -> rust::Type {
+    match db_type {
+        t if NUMERIC_TYPES.contains(&t) => map_numeric_type(t),
+        t if TEMPORAL_TYPES.contains(&t) => map_temporal_type(t),
+        t if STRING_TYPES.contains(&t) => Type::String("String"),
+        t if BINARY_TYPES.contains(&t) => Type::ByteArray("Vec<u8>"),
+        t if SYSTEM_TYPES.contains(&t) => map_system_type(t),
+        t => map_specialized_type(t),
+    }
+}
+
 // Constants for type categorization
 const NUMERIC_TYPES: &[&str] = &[
     "bool", "boolean", "smallint", "smallserial", "int2", "int", "integer",
@@ -253,8 +951,8 @@ const NUMERIC_TYPES: &[&str] = &[
 ];
 
 const TEMPORAL_TYPES: &[&str] = &[
-    "date", "time", "time without time zone", "timestamp",
-    "timestamp without time zone", "timestamp with time zone", "timestamptz",
+    "date", "time", "time without time zone", "timetz", "time with time zone", "interval",
+    "timestamp", "timestamp without time zone", "timestamp with time zone", "timestamptz",
 ];
 
 const STRING_TYPES: &[&str] = &[
@@ -264,6 +962,12 @@ const STRING_TYPES: &[&str] = &[
 
 const BINARY_TYPES: &[&str] = &["bytea"];
 
+const SYSTEM_TYPES: &[&str] = &[
+    "oid", "regclass", "regcollation", "regconfig", "regdictionary", "regnamespace",
+    "regoper", "regoperator", "regproc", "regprocedure", "regrole", "regtype",
+    "xid", "cid", "tid", "char",
+];
+
 fn map_numeric_type(typ: &str) -> rust::Type {
     match typ {
         "bool" | "boolean" => Type::Bool("bool"),
@@ -281,18 +985,49 @@ fn map_temporal_type(typ: &str) -> rust::Type {
     match typ {
         "date" => Type::Date("chrono::NaiveDate"),
         "time" | "time without time zone" => Type::Time("chrono::NaiveTime"),
+        "timetz" | "time with time zone" => Type::TimeTz("sqlx::postgres::types::PgTimeTz"),
+        "interval" => Type::Interval("sqlx::postgres::types::PgInterval"),
         "timestamp" | "timestamp without time zone" => Type::Timestamp("chrono::NaiveDateTime"),
-        "timestamp with time zone" | "timestamptz" => Type::TimestampWithTz("chrono::DateTime<Utc>"),
+        "timestamp with time zone" | "timestamptz" => {
+            Type::TimestampWithTz("chrono::DateTime<chrono::Utc>")
+        }
         _ => unreachable!("invalid temporal type"),
     }
 }
 
+fn map_system_type(typ: &str) -> rust::Type {
+    match typ {
+        "oid" => Type::U32("sqlx::postgres::types::Oid"),
+        // the single-byte internal "char" type used throughout the system catalogs
+        "char" => Type::I8("i8"),
+        // sqlx can't decode the aliases of oid, transaction/command ids and tuple identifiers, so
+        // they are surfaced in their text form, which queries must cast them to, e.g. with
+        // `relation::text`
+        "regclass" | "regcollation" | "regconfig" | "regdictionary" | "regnamespace" | "regoper"
+        | "regoperator" | "regproc" | "regprocedure" | "regrole" | "regtype" | "xid" | "cid"
+        | "tid" => Type::String("String"),
+        _ => unreachable!("invalid system type"),
+    }
+}
+
 fn map_specialized_type(typ: &str) -> rust::Type {
     match typ {
         "uuid" => Type::Uuid("uuid::Uuid"),
         "json" | "jsonb" => Type::Json("serde_json::Value"),
         "inet" | "cidr" => Type::IpNetwork("ipnetwork::IpNetwork"),
         "xml" => Type::Xml("String"),
+        "money" => Type::Money("sqlx::postgres::types::PgMoney"),
+        "int4range" | "int8range" | "numrange" | "tsrange" | "tstzrange" | "daterange" => {
+            let bound = match typ {
+                "int4range" => "int4",
+                "int8range" => "int8",
+                "numrange" => "numeric",
+                "tsrange" => "timestamp",
+                "tstzrange" => "timestamptz",
+                _ => "date",
+            };
+            Type::Range("sqlx::postgres::types::PgRange", Box::new(type_name_from(bound)))
+        }
         // Add other specialized types here
         other => Type::Custom(other.to_string()),
     }
@@ -7,16 +7,20 @@ code generation
 
 #[derive(sqlx::FromRow, Debug)]
 pub struct EnumType {
+    pub schema_name: String,
     pub name: String,
     pub value: String,
+    /// The `enumsortorder` of `pg_enum`, which is a `real`
     pub sort_order: f32,
 }
 
 #[derive(sqlx::FromRow, Debug)]
 pub struct CompositeType {
+    pub schema_name: String,
     pub name: String,
     pub attribute_name: String,
     pub data_type: String,
+    pub data_type_schema: String,
 }
 
 #[derive(sqlx::FromRow, Debug)]
@@ -24,11 +28,23 @@ pub struct TableColumn {
     pub table_name: String,
     pub column_name: String,
     pub udt_name: String,
+    pub udt_schema: String,
     pub data_type: String,
+    pub numeric_precision: Option<i32>,
+    pub numeric_scale: Option<i32>,
+    pub character_maximum_length: Option<i32>,
     pub is_nullable: bool,
+    pub column_default: Option<String>,
+    pub is_generated: bool,
     pub is_unique: bool,
     pub is_primary_key: bool,
+    pub foreign_key_schema: Option<String>,
     pub foreign_key_table: Option<String>,
     pub foreign_key_id: Option<String>,
     pub table_schema: String,
+    pub is_writable: bool,
+    pub comment: Option<String>,
+    pub table_comment: Option<String>,
+    pub is_foreign: bool,
+    pub is_materialized_view: bool,
 }
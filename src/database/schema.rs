@@ -1,26 +1,96 @@
+use std::cmp::Ordering;
+
 use anyhow::Error;
 use async_trait::async_trait;
 
 use crate::rust;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct DatabaseSchema {
     pub enumerations: Vec<Enum>,
     pub composite_types: Vec<CompositeType>,
     pub tables: Vec<Table>,
 }
 
+impl DatabaseSchema {
+    /**
+    Returns a hash of the parts of the schema the generated code depends on, i.e. the names of the types, tables and
+    columns, the values of enums and the types and nullability of columns. Comments and defaults are left out, and
+    the order the objects were retrieved in does not change the hash.
+
+    The hash is the hexadecimal FNV-1a hash of a description of the schema, which unlike the `DefaultHasher` of the
+    standard library is stable across Rust versions, so it can be compared with the hash of an introspection made by
+    a different build, e.g. the `SCHEMA_HASH` of generated code with the live database at startup.
+    */
+    pub fn hash(&self) -> String {
+        let mut lines: Vec<String> = vec![];
+        for enumeration in &self.enumerations {
+            let mut values: Vec<&EnumValue> = enumeration.values.iter().collect();
+            values.sort_by(|a, b| a.cmp_order(b));
+            let values: Vec<&str> = values.iter().map(|value| value.name.as_str()).collect();
+            lines.push(format!(
+                "enum {}.{} {}",
+                enumeration.schema,
+                enumeration.name,
+                values.join(",")
+            ));
+        }
+        for composite_type in &self.composite_types {
+            let attributes: Vec<String> = composite_type
+                .attributes
+                .iter()
+                .map(|attribute| format!("{} {}", attribute.name, attribute.data_type))
+                .collect();
+            lines.push(format!(
+                "composite {}.{} {}",
+                composite_type.schema,
+                composite_type.name,
+                attributes.join(",")
+            ));
+        }
+        for table in &self.tables {
+            let columns: Vec<String> = table
+                .columns
+                .iter()
+                .map(|column| {
+                    let nullability = if column.is_nullable {
+                        "null"
+                    } else {
+                        "not null"
+                    };
+                    format!("{} {} {nullability}", column.name, column.udt_name)
+                })
+                .collect();
+            lines.push(format!("table {} {}", table.name, columns.join(",")));
+        }
+        lines.sort();
+
+        let hash = lines
+            .join("\n")
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+        format!("{hash:016x}")
+    }
+}
+
 /**
 Contains info describing a user defined enumeration in a database
 
 # Fields
+- `schema`: The schema the enum is defined in.
 - `name`: The name of the enum.
 - `values`: The values of the enumeration
+- `backing_type`: The database type the enum is stored as, for enums that are generated from the rows of a lookup
+  table and stored as the integer key of the respective row. `None` for enums that are database types
 */
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Enum {
+    pub schema: String,
     pub name: String,
     pub values: Vec<EnumValue>,
+    pub backing_type: Option<String>,
 }
 
 /**
@@ -28,31 +98,54 @@ Contains info describing a user defined enum value in a database
 
 # Fields
 - `name`: The name of the enum value.
-- `order`: The order in which the respective value should be sorted
+- `order`: The order in which the respective value should be sorted, which is the `real` sort order of the catalog
+- `discriminant`: The key the value is stored as, for enums that have a backing type
 */
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct EnumValue {
     pub name: String,
     pub order: f32,
+    pub discriminant: Option<i64>,
+}
+
+impl EnumValue {
+    /// Compares values by their order, falling back to their name for values with the same order. The catalog doesn't
+    /// prevent those after it was edited manually, and without a tiebreak their variants would swap between runs
+    pub fn cmp_order(&self, other: &EnumValue) -> Ordering {
+        self.order
+            .total_cmp(&other.order)
+            .then_with(|| self.name.cmp(&other.name))
+    }
 }
 
 /**
 Contains info describing a user defined composite type in a database
 
 # Fields
+- `schema`: The schema the composite type is defined in.
 - `name`: The name of the composite type.
 - `attributes`: The attributes of the composite type
 */
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct CompositeType {
+    pub schema: String,
     pub name: String,
     pub attributes: Vec<Attribute>,
 }
 
-#[derive(Debug)]
+/**
+Contains info describing an attribute of a composite type
+
+# Fields
+- `name`: The name of the attribute.
+- `data_type`: The name of the data type of the attribute.
+- `data_type_schema`: The schema the data type of the attribute is defined in.
+*/
+#[derive(Clone, Debug)]
 pub struct Attribute {
     pub name: String,
     pub data_type: String,
+    pub data_type_schema: String,
 }
 
 /**
@@ -60,12 +153,20 @@ Contains info describing a table in a database
 
 # Fields
 - `name`: The name of the table.
+- `comment`: The comment attached to the table, if any
 - `columns`: The columns of the table
+- `is_foreign`: Whether the table is a foreign table, whose rows are provided by a foreign data wrapper. Foreign tables
+  can't have primary keys, unique constraints or foreign keys
+- `is_materialized_view`: Whether the table is a materialized view, whose rows are the stored result of its query
+  until it is refreshed. Like views, materialized views have no constraints, so every column is nullable
 */
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Table {
     pub name: String,
+    pub comment: Option<String>,
     pub columns: Vec<Column>,
+    pub is_foreign: bool,
+    pub is_materialized_view: bool,
 }
 
 /**
@@ -74,36 +175,91 @@ Contains info describing a column in a database table.
 # Fields
 - `name`: The name of the column.
 - `udt_name`: The underlying data type name of the column.
+- `udt_schema`: The schema the underlying data type of the column is defined in.
 - `data_type`: The data type of the column.
+- `numeric_precision`: The declared precision of the column if it is a numeric type.
+- `numeric_scale`: The declared scale of the column if it is a numeric type.
+- `character_maximum_length`: The declared length of the column if it is a character or bit string type.
 - `is_nullable`: Whether the column can contain NULL values.
+- `column_default`: The expression the column defaults to when it is omitted from an insert, if any.
+- `is_generated`: Whether the database generates the value of the column, i.e. it is an identity or a generated column.
 - `is_unique`: Whether the column has a unique constraint.
 - `is_primary_key`: Whether the column is a primary key.
+- `foreign_key_schema`: The schema of the table that this column references if it is a foreign key.
 - `foreign_key_table`: The table that this column references if it is a foreign key.
 - `foreign_key_id`: The column that this column references if it is a foreign key.
 - `table_schema`: The schema of the table.
+- `is_writable`: Whether the column can be updated by the role whose privileges are consulted, if any.
+- `comment`: The comment attached to the column, if any.
 */
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Column {
     pub name: String,
     pub udt_name: String,
+    pub udt_schema: String,
     pub data_type: String,
+    pub numeric_precision: Option<i32>,
+    pub numeric_scale: Option<i32>,
+    pub character_maximum_length: Option<i32>,
     pub is_nullable: bool,
+    pub column_default: Option<String>,
+    pub is_generated: bool,
     pub is_unique: bool,
     pub is_primary_key: bool,
+    pub foreign_key_schema: Option<String>,
     pub foreign_key_table: Option<String>,
     pub foreign_key_id: Option<String>,
     pub table_schema: String,
+    pub is_writable: bool,
+    pub comment: Option<String>,
 }
 
 /**
 The `schema::InfoProvider` trait defines a common interface for retrieving database schema information from a database.
+It is the only trait a new backend implements, and the `DatabaseSchema` it returns is the only schema model the
+generator consumes.
 
 # Methods
 - `type_name_from`: returns the Rust type name from database column info
-- `get_table_info`: Asynchronously retrieves a list of `TableColumn` structs representing the columns in the database's tables.
+- `get_schema`: Asynchronously retrieves the enums, composite types and tables of the database as a `DatabaseSchema`.
+  Backends retrieving them with several queries should run these in a single snapshot of the database, such as a
+  `REPEATABLE READ` transaction, so a migration running concurrently can't be observed halfway.
 */
 #[async_trait]
 pub trait InfoProvider {
     fn type_name_from(&self, db_type: &str) -> rust::Type;
     async fn get_schema(&self) -> Result<DatabaseSchema, Error>;
 }
+
+/**
+The `schema::SyncInfoProvider` trait is the counterpart of `InfoProvider` for backends whose driver is synchronous,
+such as `rusqlite` for SQLite. Such a backend doesn't need an async runtime, so it can generate code with
+`generator::run_blocking` without starting tokio, or be passed to anything expecting an `InfoProvider` by wrapping it
+in `Blocking`.
+
+# Methods
+- `type_name_from`: returns the Rust type name from database column info
+- `get_schema`: Retrieves the enums, composite types and tables of the database as a `DatabaseSchema`, blocking the
+  calling thread.
+*/
+pub trait SyncInfoProvider {
+    fn type_name_from(&self, db_type: &str) -> rust::Type;
+    fn get_schema(&self) -> Result<DatabaseSchema, Error>;
+}
+
+/**
+Wraps a `SyncInfoProvider` so it can be used as an `InfoProvider`. The schema is retrieved on the thread that polls
+`get_schema`, which completes on the first poll since nothing is awaited, so the future doesn't depend on a runtime.
+*/
+pub struct Blocking<P>(pub P);
+
+#[async_trait]
+impl<P: SyncInfoProvider + Send + Sync> InfoProvider for Blocking<P> {
+    fn type_name_from(&self, db_type: &str) -> rust::Type {
+        self.0.type_name_from(db_type)
+    }
+
+    async fn get_schema(&self) -> Result<DatabaseSchema, Error> {
+        self.0.get_schema()
+    }
+}
@@ -1 +1,54 @@
+/*!
+Maps SQLite column types to Rust types.
 
+SQLite columns have a type affinity rather than a rigid type: any name can be declared as the type of a column, and
+the affinity derived from it only decides how values are preferably stored. Tables declared `STRICT` instead only
+accept the types `INT`, `INTEGER`, `REAL`, `TEXT`, `BLOB` and `ANY`, which their values are enforced to have.
+*/
+
+use crate::rust::{self, Type};
+
+/**
+Maps the declared type of a SQLite column (as reported by `pragma_table_info`) to its Rust type. This does not require
+a connection, which allows the mapping to be used when generating code from a schema that was not read from a live
+database.
+
+The declared types of `STRICT` tables are mapped exactly. Their `ANY` columns store values as they are given, like
+columns without a declared type, so they map to `Vec<u8>` as well. Columns of other tables are mapped by the affinity
+of their declared type, following the rules of SQLite in their order of precedence:
+- a type containing `INT` has integer affinity and maps to `i64`
+- a type containing `CHAR`, `CLOB` or `TEXT` has text affinity and maps to `String`
+- a type containing `BLOB`, or no declared type, has blob affinity and maps to `Vec<u8>`
+- a type containing `REAL`, `FLOA` or `DOUB` has real affinity and maps to `f64`
+- any other type, such as `NUMERIC`, `DECIMAL` or `BOOLEAN`, has numeric affinity and maps to `f64`, since numeric
+  values are stored as integers or reals depending on whether they have a fractional part
+
+Since the rules look for substrings, `FLOATING POINT` contains `INT` and has integer affinity, just like in SQLite.
+*/
+pub fn type_name_from(declared_type: &str, is_strict: bool) -> rust::Type {
+    let declared_type = declared_type.trim().to_uppercase();
+    if is_strict {
+        return match declared_type.as_str() {
+            "INT" | "INTEGER" => Type::I64("i64"),
+            "REAL" => Type::F64("f64"),
+            "TEXT" => Type::String("String"),
+            "BLOB" => Type::ByteArray("Vec<u8>"),
+            // ANY columns keep values of every storage class, which are read as the bytes of their value
+            _ => Type::ByteArray("Vec<u8>"),
+        };
+    }
+
+    if declared_type.contains("INT") {
+        Type::I64("i64")
+    } else if ["CHAR", "CLOB", "TEXT"]
+        .iter()
+        .any(|name| declared_type.contains(name))
+    {
+        Type::String("String")
+    } else if declared_type.is_empty() || declared_type.contains("BLOB") {
+        Type::ByteArray("Vec<u8>")
+    } else {
+        // real and numeric affinity
+        Type::F64("f64")
+    }
+}
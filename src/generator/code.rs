@@ -1,12 +1,72 @@
+use super::GeneratorError;
 use crate::{database, rust::Type};
-use anyhow::Error;
+use clap::ValueEnum;
 use cruet::Inflector;
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
 
 pub struct Snippet {
     pub id: String,
-    pub imports: HashSet<String>,
+    pub imports: BTreeSet<String>,
     pub code: String,
+    /// Types defined by the snippet besides its main type (e.g. typed IDs), which are re-exported alongside it
+    pub exports: Vec<String>,
+    /// The name of the module the snippet is written to when it isn't the snake case form of its id, e.g. the plural
+    /// `users` module of a singular `User` struct
+    pub module_name: Option<String>,
+}
+
+impl Snippet {
+    /// Returns the name of the module, and thereby the file, the snippet is written to
+    pub fn module(&self) -> String {
+        self.module_name
+            .clone()
+            .unwrap_or_else(|| self.id.to_snake_case())
+    }
+
+    /// Registers a fully qualified path (e.g. `chrono::NaiveDate`) that the snippet's code depends on
+    pub fn add_import(&mut self, path: &str) {
+        self.imports.insert(path.to_string());
+    }
+
+    /// Registers every import required for the provided type to resolve
+    pub fn add_type_imports(&mut self, rust_type: &Type) {
+        for path in rust_type.imports() {
+            self.add_import(&path);
+        }
+    }
+
+    /// Returns the source code of the snippet preceded by its `use` declarations.
+    /// Imports from the same module are coalesced into a single sorted, braced `use`
+    pub fn finalize(&self) -> String {
+        let modules =
+            self.imports
+                .iter()
+                .fold(BTreeMap::<&str, BTreeSet<&str>>::new(), |mut acc, path| {
+                    if let Some((module, item)) = path.rsplit_once("::") {
+                        acc.entry(module).or_default().insert(item);
+                    }
+                    acc
+                });
+
+        let mut code = String::new();
+        for (module, items) in &modules {
+            let items: Vec<&str> = items.iter().copied().collect();
+            match items.as_slice() {
+                [item] => code.push_str(&format!("use {module}::{item};\n")),
+                _ => code.push_str(&format!("use {module}::{{{}}};\n", items.join(", "))),
+            }
+        }
+
+        if !code.is_empty() {
+            code.push('\n');
+        }
+        code.push_str(&self.code);
+        code
+    }
 }
 
 /**
@@ -14,119 +74,2833 @@ Contains fields that indicate formatting options that should be applied to the g
 
 # Fields
 - `singular`: specifies with the generated Rust structs name should be the singular form the provided tables
+- `singular_modules`: specifies whether the file and module names of table structs should be the singular form of the
+  table name, independently of the struct names
+- `framework`: specifies the database framework the generated code should integrate with. Without one, types of sqlx
+  are replaced by plain alternatives
+- `derives`: additional traits that should be derived for the generated types, where the field types support them
+- `struct_attributes`: attributes that are placed verbatim above every generated struct and enum
+- `non_exhaustive_enums`: specifies whether enums should be marked `#[non_exhaustive]`, with a `#[serde(other)]` catch-all
+  variant when they derive `Deserialize` without a framework, so labels added to the database type don't break existing
+  consumers. sqlx can't decode unknown labels into that variant, which `enum_unknown_variant` is for
+- `enum_unknown_variant`: specifies whether enums should have an `Unknown(String)` variant that labels missing from the
+  enum are decoded into, which replaces the `sqlx::Type` derive with a hand written implementation
+- `enum_try_from`: specifies whether enums should implement `TryFrom<&str>`, converting their database labels into variants
+- `derive_copy_when_possible`: specifies whether `Copy` (and `Clone`) should be derived for types whose fields are all `Copy`
+- `typed_ids`: specifies whether tables with a single column primary key should get an ID newtype (e.g. `UserId`), which
+  the primary key and the foreign keys referencing it are represented as. Foreign tables never get one
+- `phantom_ids`: specifies whether typed IDs should be aliases of a generic `Id<T>` (e.g. `type UserId = Id<Users>`)
+  that is generated once, instead of a newtype per table
+- `citext_newtype`: specifies whether `citext` columns should be represented as a generated `CiText` newtype whose
+  equality is case insensitive, instead of a `String`
+- `serde_null_as_missing`: specifies whether fields of nullable columns should be skipped when serializing `None` and
+  default to `None` when missing while deserializing, when serde's `Serialize` or `Deserialize` is derived
+- `interval_struct`: specifies whether `interval` columns should be represented as a generated `Interval` struct of
+  their months, days and microseconds, instead of the type selected by `interval_format`
+- `annotate_types`: specifies whether each field should be documented with the database type it was generated from
+- `annotate_defaults`: specifies whether fields of columns with a default should be documented with its expression
+- `types_only`: specifies whether only enums and composite types should be generated, skipping the structs of tables
+- `column_types`: Rust types keyed by `table.column` that override the type a column is mapped to
+- `bitflags`: the names and values of flags keyed by `table.column` of integer columns that are represented as a
+  `bitflags` type
+- `display_fields`: the columns keyed by the name of their table whose field the struct of the table is displayed as
+- `nullable_columns`: columns in the form of `table.column` that are represented as `Option` regardless of their nullability
+- `not_null_columns`: columns in the form of `table.column` that are never represented as `Option` regardless of their nullability
+- `nullable_array_elements`: specifies whether the elements of arrays should be represented as `Option`, since PostgreSQL
+  arrays can contain NULL elements regardless of the nullability of their column
+- `money_as_decimal`: specifies whether money columns should be represented as `rust_decimal::Decimal` instead of `PgMoney`.
+  Without a framework they always are, since `PgMoney` is a type of sqlx
+- `interval_format`: specifies the Rust type interval columns should be represented as. When it isn't set, the
+  framework decides: `Duration` without a framework, so the code doesn't depend on sqlx, and `Pg` with sqlx
+- `numeric_as_integer`: specifies whether numeric columns with a scale of 0 that fit into an `i64` should be represented as one
+- `bytea_format`: specifies the Rust type bytea columns should be represented as
+- `constructors`: specifies whether a `new` function taking all non optional fields should be generated for tables
+- `compact_flags`: specifies whether `bit(1)` and `char(1)` columns should be represented as `bool` and `char`
+- `fixed_binary`: specifies whether `bit(n)` columns whose length is a multiple of 8 should be represented as `[u8; n / 8]`
+- `sqlx_default_options`: specifies whether optional fields should default to `None` when absent from a row decoded via `sqlx::FromRow`
+- `indent`: specifies the indentation of the generated code
+- `rename_on_collision`: specifies which type is renamed when a table and an enum or composite type share a name
+- `manual_from_row`: specifies whether `sqlx::FromRow` should be implemented by hand instead of derived, so the mapping can be edited after generation
+- `query_macros`: specifies whether tables should get functions fetching their rows with the `sqlx::query_as!` macro, which
+  verifies the queries against the database at compile time
+- `column_list`: specifies whether table structs should get a `columns` function returning the names of their columns in order
+- `crud`: specifies whether tables should get an insert struct and functions inserting, updating and deleting their rows
 */
+#[derive(Default)]
 pub struct Options {
     pub singular: bool,
+    pub singular_modules: bool,
+    pub framework: Framework,
+    pub derives: Vec<String>,
+    pub struct_attributes: Vec<String>,
+    pub derive_copy_when_possible: bool,
+    pub non_exhaustive_enums: bool,
+    pub enum_unknown_variant: bool,
+    pub enum_try_from: bool,
+    pub typed_ids: bool,
+    pub phantom_ids: bool,
+    pub citext_newtype: bool,
+    pub serde_null_as_missing: bool,
+    pub interval_struct: bool,
+    pub annotate_types: bool,
+    pub annotate_defaults: bool,
+    pub types_only: bool,
+    pub column_types: HashMap<String, String>,
+    pub bitflags: HashMap<String, Vec<(String, u64)>>,
+    pub display_fields: HashMap<String, String>,
+    pub nullable_columns: HashSet<String>,
+    pub not_null_columns: HashSet<String>,
+    pub nullable_array_elements: bool,
+    pub money_as_decimal: bool,
+    pub interval_format: Option<IntervalFormat>,
+    pub numeric_as_integer: bool,
+    pub bytea_format: ByteaFormat,
+    pub constructors: bool,
+    pub compact_flags: bool,
+    pub fixed_binary: bool,
+    pub sqlx_default_options: bool,
+    pub manual_from_row: bool,
+    pub query_macros: bool,
+    pub column_list: bool,
+    pub crud: bool,
+    pub rename_on_collision: NameCollision,
+    pub indent: Indent,
+}
+
+/**
+Setters that allow configuring the generation programmatically, starting from the defaults of the command line:
+
+```
+use autostruct::generator::{Framework, Options};
+
+let options = Options::default()
+    .framework(Framework::Sqlx)
+    .derives(["Debug", "Clone"])
+    .singular(true);
+```
+*/
+impl Options {
+    /// Sets whether the names of table structs are the singular form of the table name
+    pub fn singular(mut self, enabled: bool) -> Self {
+        self.singular = enabled;
+        self
+    }
+
+    /// Sets whether the file and module names of table structs are the singular form of the table name
+    pub fn singular_modules(mut self, enabled: bool) -> Self {
+        self.singular_modules = enabled;
+        self
+    }
+
+    /// Sets the database framework the generated code integrates with
+    pub fn framework(mut self, framework: Framework) -> Self {
+        self.framework = framework;
+        self
+    }
+
+    /// Sets the traits that are derived for the generated types, where the field types support them
+    pub fn derives<I, S>(mut self, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.derives = derives.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the attributes that are placed verbatim above every generated struct and enum
+    pub fn struct_attributes<I, S>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.struct_attributes = attributes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets whether `Copy` and `Clone` are derived for types whose fields are all `Copy`
+    pub fn derive_copy_when_possible(mut self, enabled: bool) -> Self {
+        self.derive_copy_when_possible = enabled;
+        self
+    }
+
+    /// Sets whether enums are marked `#[non_exhaustive]`
+    pub fn non_exhaustive_enums(mut self, enabled: bool) -> Self {
+        self.non_exhaustive_enums = enabled;
+        self
+    }
+
+    /// Sets whether enums get an `Unknown(String)` variant that unknown labels are decoded into
+    pub fn enum_unknown_variant(mut self, enabled: bool) -> Self {
+        self.enum_unknown_variant = enabled;
+        self
+    }
+
+    /// Sets whether enums implement `TryFrom<&str>` for their database labels
+    pub fn enum_try_from(mut self, enabled: bool) -> Self {
+        self.enum_try_from = enabled;
+        self
+    }
+
+    /// Sets whether tables with a single column primary key get an ID newtype
+    pub fn typed_ids(mut self, enabled: bool) -> Self {
+        self.typed_ids = enabled;
+        self
+    }
+
+    /// Sets whether typed IDs are aliases of a generic `Id<T>` that is phantom typed by the struct of their table
+    pub fn phantom_ids(mut self, enabled: bool) -> Self {
+        self.phantom_ids = enabled;
+        self
+    }
+
+    /// Sets whether `citext` columns are represented as the case insensitive `CiText` newtype
+    pub fn citext_newtype(mut self, enabled: bool) -> Self {
+        self.citext_newtype = enabled;
+        self
+    }
+
+    /// Sets whether fields of nullable columns are left out when serializing `None` and deserializing missing fields
+    pub fn serde_null_as_missing(mut self, enabled: bool) -> Self {
+        self.serde_null_as_missing = enabled;
+        self
+    }
+
+    /// Sets whether `interval` columns are represented as the generated `Interval` struct
+    pub fn interval_struct(mut self, enabled: bool) -> Self {
+        self.interval_struct = enabled;
+        self
+    }
+
+    /// Sets whether each field is documented with the database type it was generated from
+    pub fn annotate_types(mut self, enabled: bool) -> Self {
+        self.annotate_types = enabled;
+        self
+    }
+
+    /// Sets whether fields of columns with a default are documented with its expression
+    pub fn annotate_defaults(mut self, enabled: bool) -> Self {
+        self.annotate_defaults = enabled;
+        self
+    }
+
+    /// Sets whether only enums and composite types are generated
+    pub fn types_only(mut self, enabled: bool) -> Self {
+        self.types_only = enabled;
+        self
+    }
+
+    /// Sets the Rust type a column in the form of `table.column` is represented as, overriding its mapping
+    pub fn column_type(mut self, column: impl Into<String>, rust_type: impl Into<String>) -> Self {
+        self.column_types.insert(column.into(), rust_type.into());
+        self
+    }
+
+    /// Sets an integer column in the form of `table.column` to be represented as a `bitflags` type with the provided
+    /// flags, e.g. `[("READ", 1), ("WRITE", 2)]`
+    pub fn bitflags<S: Into<String>>(
+        mut self,
+        column: impl Into<String>,
+        flags: impl IntoIterator<Item = (S, u64)>,
+    ) -> Self {
+        let flags = flags
+            .into_iter()
+            .map(|(name, value)| (name.into(), value))
+            .collect();
+        self.bitflags.insert(column.into(), flags);
+        self
+    }
+
+    /// Sets the column whose field the struct of a table implements `Display` with, e.g. `("users", "email")`
+    pub fn display_field(mut self, table: impl Into<String>, column: impl Into<String>) -> Self {
+        self.display_fields.insert(table.into(), column.into());
+        self
+    }
+
+    /// Sets a column in the form of `table.column` to be represented as an `Option` regardless of its nullability
+    pub fn nullable_column(mut self, column: impl Into<String>) -> Self {
+        self.nullable_columns.insert(column.into());
+        self
+    }
+
+    /// Sets a column in the form of `table.column` to never be represented as an `Option` regardless of its nullability
+    pub fn not_null_column(mut self, column: impl Into<String>) -> Self {
+        self.not_null_columns.insert(column.into());
+        self
+    }
+
+    /// Sets whether the elements of arrays are represented as `Option`
+    pub fn nullable_array_elements(mut self, enabled: bool) -> Self {
+        self.nullable_array_elements = enabled;
+        self
+    }
+
+    /// Sets whether money columns are represented as `rust_decimal::Decimal`
+    pub fn money_as_decimal(mut self, enabled: bool) -> Self {
+        self.money_as_decimal = enabled;
+        self
+    }
+
+    /// Sets the Rust type interval columns are represented as, instead of the default of the framework
+    pub fn interval_format(mut self, interval_format: IntervalFormat) -> Self {
+        self.interval_format = Some(interval_format);
+        self
+    }
+
+    /// Sets whether numeric columns with a scale of 0 that fit into an `i64` are represented as one
+    pub fn numeric_as_integer(mut self, enabled: bool) -> Self {
+        self.numeric_as_integer = enabled;
+        self
+    }
+
+    /// Sets the Rust type bytea columns are represented as
+    pub fn bytea_format(mut self, bytea_format: ByteaFormat) -> Self {
+        self.bytea_format = bytea_format;
+        self
+    }
+
+    /// Sets whether a `new` function taking all non optional fields is generated for tables
+    pub fn constructors(mut self, enabled: bool) -> Self {
+        self.constructors = enabled;
+        self
+    }
+
+    /// Sets whether `bit(1)` and `char(1)` columns are represented as `bool` and `char`
+    pub fn compact_flags(mut self, enabled: bool) -> Self {
+        self.compact_flags = enabled;
+        self
+    }
+
+    /// Sets whether `bit(n)` columns whose length is a multiple of 8 are represented as `[u8; n / 8]`
+    pub fn fixed_binary(mut self, enabled: bool) -> Self {
+        self.fixed_binary = enabled;
+        self
+    }
+
+    /// Sets whether optional fields default to `None` when absent from a row
+    pub fn sqlx_default_options(mut self, enabled: bool) -> Self {
+        self.sqlx_default_options = enabled;
+        self
+    }
+
+    /// Sets whether `sqlx::FromRow` is implemented by hand instead of derived
+    pub fn manual_from_row(mut self, enabled: bool) -> Self {
+        self.manual_from_row = enabled;
+        self
+    }
+
+    /// Sets whether tables get functions fetching their rows with the compile time checked `sqlx::query_as!` macro
+    pub fn query_macros(mut self, enabled: bool) -> Self {
+        self.query_macros = enabled;
+        self
+    }
+
+    /// Sets whether table structs get a `columns` function returning the names of their columns in order
+    pub fn column_list(mut self, enabled: bool) -> Self {
+        self.column_list = enabled;
+        self
+    }
+
+    /// Sets whether tables get an insert struct and functions inserting, updating and deleting their rows
+    pub fn crud(mut self, enabled: bool) -> Self {
+        self.crud = enabled;
+        self
+    }
+
+    /// Sets which type is renamed when a table and an enum or composite type share a name
+    pub fn rename_on_collision(mut self, rename_on_collision: NameCollision) -> Self {
+        self.rename_on_collision = rename_on_collision;
+        self
+    }
+
+    /// Sets the indentation of the generated code
+    pub fn indent(mut self, indent: Indent) -> Self {
+        self.indent = indent;
+        self
+    }
+}
+
+/**
+The database frameworks the generated code can integrate with. This is the single definition used by
+both the library and the command line, so supporting a new framework only requires adding a variant here
+
+# Variants
+- `None`: plain structs without any framework specific code
+- `Sqlx`: structs deriving `sqlx::FromRow` so they can be used with `sqlx::query_as`
+*/
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Framework {
+    #[default]
+    None,
+    Sqlx,
+}
+
+/**
+The Rust types an interval column can be represented as
+
+# Variants
+- `Pg`: `sqlx::postgres::types::PgInterval`, which preserves the months, days and microseconds of the interval
+- `Duration`: `chrono::Duration`, which sqlx can only encode, so this can't be used with `Framework::Sqlx`. An
+  interval containing months can not be converted into a fixed duration, so this should only be used when the
+  intervals stored are known to be shorter than a month
+*/
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IntervalFormat {
+    #[default]
+    Pg,
+    Duration,
+}
+
+/**
+The Rust types a bytea column can be represented as
+
+# Variants
+- `Vec`: `Vec<u8>`
+- `Bytes`: `bytes::Bytes`, a reference counted buffer that avoids copying the data
+*/
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ByteaFormat {
+    #[default]
+    Vec,
+    Bytes,
+}
+
+/**
+The type that is renamed when a table and an enum or composite type would be generated with the same name
+
+# Variants
+- `Type`: the enum is suffixed with `Enum` and the composite type with `Composite`
+- `Table`: the table struct is suffixed with `Row`
+*/
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NameCollision {
+    #[default]
+    Type,
+    Table,
+}
+
+/**
+The indentation of the generated code
+
+# Variants
+- `Spaces`: indents each level with the provided number of spaces
+- `Tabs`: indents each level with a tab
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indent {
+    Spaces(usize),
+    Tabs,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Spaces(Self::LEVEL_WIDTH)
+    }
+}
+
+impl Indent {
+    /// The number of spaces each level of the code is indented with while it is generated
+    const LEVEL_WIDTH: usize = 4;
+
+    /// Re-indents code that is indented with four spaces per level
+    pub fn apply(&self, code: &str) -> String {
+        if *self == Indent::default() {
+            return code.to_string();
+        }
+
+        let unit = match self {
+            Indent::Spaces(width) => " ".repeat(*width),
+            Indent::Tabs => "\t".to_string(),
+        };
+        code.split('\n')
+            .map(|line| {
+                let content = line.trim_start_matches(' ');
+                let levels = (line.len() - content.len()) / Self::LEVEL_WIDTH;
+                let remainder = (line.len() - content.len()) % Self::LEVEL_WIDTH;
+                format!("{}{}{content}", unit.repeat(levels), " ".repeat(remainder))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/**
+The line endings of the generated files
+
+# Variants
+- `Lf`: `\n`, as used on Unix
+- `Crlf`: `\r\n`, as used on Windows
+*/
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    /// Converts the `\n` line endings of generated code to the line ending
+    pub fn apply(&self, code: &str) -> String {
+        match self {
+            LineEnding::Lf => code.to_string(),
+            LineEnding::Crlf => code.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/**
+The kinds of database objects that code is generated for
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectKind {
+    Enum,
+    Composite,
+    Table,
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ObjectKind::Enum => "enum",
+            ObjectKind::Composite => "composite",
+            ObjectKind::Table => "table",
+        };
+        f.pad(kind)
+    }
+}
+
+/**
+Describes the Rust type that is generated for a database object, without generating its code
+
+# Fields
+- `kind`: the kind of the database object
+- `name`: the name of the database object
+- `type_name`: the name of the generated Rust type
+- `unknown_types`: database types used by the object that are neither built in nor defined in the schema. These are
+  mapped to a custom Rust type of the same name, which has to be provided by the consumer of the generated code
+*/
+pub struct Entry {
+    pub kind: ObjectKind,
+    pub name: String,
+    pub type_name: String,
+    pub unknown_types: Vec<String>,
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<9} {} -> {}", self.kind, self.name, self.type_name)?;
+        if !self.unknown_types.is_empty() {
+            write!(f, " (unknown types: {})", self.unknown_types.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/**
+Describes the Rust type a column is mapped to, without generating its code
+
+# Fields
+- `table`: the name of the table the column belongs to
+- `column`: the name of the column
+- `db_type`: the database type of the column, including its declared length or precision
+- `rust_type`: the Rust type of the field the column is represented as
+*/
+pub struct Mapping {
+    pub table: String,
+    pub column: String,
+    pub db_type: String,
+    pub rust_type: Type,
+}
+
+impl Mapping {
+    /// Returns whether the column is mapped to a custom type, e.g. an enum of the schema, a type provided with
+    /// `column_types` or a type the database type isn't known for
+    pub fn is_custom(&self) -> bool {
+        is_custom(&self.rust_type)
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}: {} -> {}",
+            self.table, self.column, self.db_type, self.rust_type
+        )?;
+        if self.is_custom() {
+            write!(f, " (custom)")?;
+        }
+        Ok(())
+    }
 }
 
 pub struct Generator {
     formatting: Options,
-    provider: Box<dyn database::InfoProvider>,
+    provider: Arc<dyn database::InfoProvider>,
 }
 
 impl Generator {
     pub fn new(formatting: Options, provider: Box<dyn database::InfoProvider>) -> Self {
+        Self::with_shared_provider(formatting, provider.into())
+    }
+
+    /// Creates a generator that shares its provider with other generators, e.g. to generate several outputs from
+    /// the same connection
+    pub fn with_shared_provider(
+        formatting: Options,
+        provider: Arc<dyn database::InfoProvider>,
+    ) -> Self {
         Generator {
             formatting,
             provider,
         }
     }
 
-    pub async fn generate_code(&self) -> Result<Vec<Snippet>, Error> {
-        let schema = self.provider.get_schema().await?;
+    pub async fn generate_code(&self) -> Result<Vec<Snippet>, GeneratorError> {
+        let schema = self
+            .provider
+            .get_schema()
+            .await
+            .map_err(GeneratorError::Introspection)?;
+        Ok(self.generate_code_from(&schema))
+    }
+
+    /// Generates code from an already retrieved schema. The provider is only used to map database types to Rust types
+    pub fn generate_code_from(&self, schema: &database::DatabaseSchema) -> Vec<Snippet> {
         let mut snippets: Vec<Snippet> = vec![];
-        snippets.append(&mut self.code_from_enums(&schema.enumerations));
-        snippets.append(&mut self.code_from_composites(&schema.composite_types));
-        snippets.append(&mut self.code_from_tables(&schema.tables));
+        let user_types = self.user_types(schema);
+        let type_derives = self.type_derives(schema, &user_types);
+        snippets.append(&mut self.code_from_enums(
+            &schema.enumerations,
+            &user_types,
+            &type_derives,
+        ));
+        snippets.append(&mut self.code_from_composites(
+            &schema.composite_types,
+            &user_types,
+            &type_derives,
+        ));
+        // the structs of tables are left to the crate using the types, e.g. when they are shared between crates
+        if !self.formatting.types_only {
+            snippets.append(&mut self.code_from_tables(&schema.tables, &user_types, &type_derives));
+            if self.formatting.citext_newtype && uses_citext(&schema.tables) {
+                snippets.push(self.code_for_citext());
+            }
+            if self.formatting.interval_struct && uses_interval(&schema.tables) {
+                snippets.push(self.code_for_interval());
+            }
+        }
+        for snippet in &mut snippets {
+            snippet.code = self.formatting.indent.apply(&snippet.code);
+        }
+        snippets
+    }
+
+    /// Lists the types that would be generated for the database schema without generating their code
+    pub async fn list_entries(&self) -> Result<Vec<Entry>, GeneratorError> {
+        let schema = self
+            .provider
+            .get_schema()
+            .await
+            .map_err(GeneratorError::Introspection)?;
+        Ok(self.list_entries_from(&schema))
+    }
+
+    /// Lists the types that would be generated for an already retrieved schema
+    pub fn list_entries_from(&self, schema: &database::DatabaseSchema) -> Vec<Entry> {
+        let known = self.user_types(schema);
+        let unknown_types = |types: Vec<Type>| -> Vec<String> {
+            types
+                .iter()
+                .filter_map(|rust_type| unknown_type(rust_type, &known))
+                .collect::<BTreeSet<String>>()
+                .into_iter()
+                .collect()
+        };
+
+        let mut entries: Vec<Entry> = vec![];
+        for e in &schema.enumerations {
+            entries.push(Entry {
+                kind: ObjectKind::Enum,
+                name: e.name.clone(),
+                type_name: known[&(e.schema.as_str(), e.name.as_str())].clone(),
+                unknown_types: vec![],
+            });
+        }
+
+        for composite in &schema.composite_types {
+            let types = composite
+                .attributes
+                .iter()
+                .map(|attr| self.rust_type_from(&attr.data_type))
+                .collect();
+            entries.push(Entry {
+                kind: ObjectKind::Composite,
+                name: composite.name.clone(),
+                type_name: known[&(composite.schema.as_str(), composite.name.as_str())].clone(),
+                unknown_types: unknown_types(types),
+            });
+        }
+
+        let tables: &[database::Table] = match self.formatting.types_only {
+            true => &[],
+            false => &schema.tables,
+        };
+        for table in tables {
+            let Some(table_name) = self.table_name(table, &known) else {
+                continue;
+            };
+            let types = table
+                .columns
+                .iter()
+                .filter(|column| !Annotations::parse(column.comment.as_deref()).skip)
+                // overridden columns are mapped to a type the user provided on purpose
+                .filter(|column| {
+                    !self
+                        .formatting
+                        .column_types
+                        .contains_key(&format!("{}.{}", table.name, column.name))
+                })
+                .map(|column| self.column_type(&table.name, column))
+                .collect();
+            entries.push(Entry {
+                kind: ObjectKind::Table,
+                name: table.name.clone(),
+                type_name: table_name.to_pascal_case(),
+                unknown_types: unknown_types(types),
+            });
+        }
 
-        Ok(snippets)
+        entries
+    }
+
+    /// Lists the Rust type every generated column of the database schema is mapped to
+    pub async fn list_mappings(&self) -> Result<Vec<Mapping>, GeneratorError> {
+        let schema = self
+            .provider
+            .get_schema()
+            .await
+            .map_err(GeneratorError::Introspection)?;
+        Ok(self.list_mappings_from(&schema))
+    }
+
+    /// Lists the Rust type every generated column of an already retrieved schema is mapped to
+    pub fn list_mappings_from(&self, schema: &database::DatabaseSchema) -> Vec<Mapping> {
+        if self.formatting.types_only {
+            return vec![];
+        }
+
+        let user_types = self.user_types(schema);
+        let mut mappings: Vec<Mapping> = vec![];
+        for table in &schema.tables {
+            if self.base_table_name(table).is_none() {
+                continue;
+            }
+            for column in &table.columns {
+                if Annotations::parse(column.comment.as_deref()).skip {
+                    continue;
+                }
+                let rust_type = reference_user_types(
+                    self.column_type(&table.name, column),
+                    &column.udt_schema,
+                    &user_types,
+                );
+                let mut rust_type = self.with_element_nullability(rust_type);
+                if self.is_nullable(&table.name, column) {
+                    rust_type = Type::Option(Box::new(rust_type))
+                }
+                mappings.push(Mapping {
+                    table: table.name.clone(),
+                    column: column.name.clone(),
+                    db_type: sql_type(column),
+                    rust_type,
+                });
+            }
+        }
+        mappings
     }
 
-    fn code_from_enums(&self, enums: &[database::Enum]) -> Vec<Snippet> {
+    fn code_from_enums(
+        &self,
+        enums: &[database::Enum],
+        user_types: &UserTypes,
+        type_derives: &TypeDerives,
+    ) -> Vec<Snippet> {
         enums
             .iter()
             .map(|e| {
+                let name = user_types[&(e.schema.as_str(), e.name.as_str())].clone();
+                let mut snippet = Snippet {
+                    id: name.clone(),
+                    imports: Default::default(),
+                    code: String::new(),
+                    exports: vec![],
+                    module_name: None,
+                };
                 let mut code = String::new();
-                let name = e.name.to_pascal_case();
+                let backing_type = e.backing_type.as_deref().map(|t| self.rust_type_from(t));
+                // variants of enums with discriminants must be unit variants
+                let unknown_variant =
+                    self.formatting.enum_unknown_variant && backing_type.is_none();
+                let manual_sqlx_type =
+                    unknown_variant && self.formatting.framework == Framework::Sqlx;
+                let fields = self.enum_fields(e);
+                code.push_str(&self.type_attributes());
+                if let Some(derive) = self.derive_attribute(&name, &fields, type_derives) {
+                    code.push_str(&derive);
+                }
+                match (&backing_type, self.formatting.framework) {
+                    // sqlx encodes enums with a primitive representation as that primitive
+                    (Some(_), Framework::Sqlx) => code.push_str("#[derive(sqlx::Type)]\n"),
+                    (None, Framework::Sqlx) if !manual_sqlx_type => {
+                        code.push_str(&code_for_sqlx_type(&e.schema, &e.name))
+                    }
+                    (_, _) => {}
+                }
+                if let Some(backing_type) = &backing_type {
+                    code.push_str(&format!("#[repr({backing_type})]\n"));
+                }
+                if self.formatting.non_exhaustive_enums {
+                    code.push_str("#[non_exhaustive]\n");
+                }
                 code.push_str(&format!("pub enum {} {{\n", name));
 
                 for value in &e.values {
                     let field_name = value.name.to_pascal_case();
+                    if let Some(discriminant) = value.discriminant {
+                        code.push_str(&format!("    {field_name} = {discriminant},\n"));
+                        continue;
+                    }
+                    // sqlx decodes variants by their name unless they are renamed to their label
+                    if self.formatting.framework == Framework::Sqlx
+                        && !manual_sqlx_type
+                        && field_name != value.name
+                    {
+                        code.push_str(&format!(
+                            "    #[sqlx(rename = {})]\n",
+                            string_literal(&value.name)
+                        ));
+                    }
                     let enum_field = format!("    {field_name},\n");
                     code.push_str(&enum_field);
                 }
 
+                // labels unknown to the enum are deserialized into a catch-all variant, which can't be
+                // done for enums with discriminants since serde(other) requires a unit variant
+                if unknown_variant {
+                    code.push_str("    Unknown(String),\n");
+                } else if self.formatting.non_exhaustive_enums
+                    && backing_type.is_none()
+                    && self.derives("Deserialize")
+                {
+                    match self.formatting.framework {
+                        Framework::None => code.push_str("    #[serde(other)]\n    Unknown,\n"),
+                        // sqlx would encode the unit variant as the label `Unknown` and still fail to decode
+                        // unknown labels, so only the variant of --enum-unknown-variant works with it
+                        Framework::Sqlx => eprintln!(
+                            "warning: enum {name} gets no #[serde(other)] Unknown variant since sqlx can't decode unknown labels into it, use --enum-unknown-variant to decode them into an Unknown(String) variant"
+                        ),
+                    }
+                }
+
                 code.push('}');
 
-                Snippet {
-                    id: name,
-                    imports: Default::default(),
-                    code,
+                if self.formatting.enum_try_from {
+                    code.push_str(&code_for_try_from(&name, e, unknown_variant));
+                }
+
+                if manual_sqlx_type {
+                    for path in [
+                        "sqlx::Postgres",
+                        "sqlx::encode::IsNull",
+                        "sqlx::error::BoxDynError",
+                        "sqlx::postgres::PgArgumentBuffer",
+                        "sqlx::postgres::PgHasArrayType",
+                        "sqlx::postgres::PgTypeInfo",
+                        "sqlx::postgres::PgValueRef",
+                    ] {
+                        snippet.add_import(path);
+                    }
+                    code.push_str(&code_for_sqlx_type_impl(&name, e));
                 }
+
+                snippet.code = code;
+                snippet
             })
             .collect()
     }
 
-    fn code_from_composites(&self, composites: &[database::CompositeType]) -> Vec<Snippet> {
+    fn code_from_composites(
+        &self,
+        composites: &[database::CompositeType],
+        user_types: &UserTypes,
+        type_derives: &TypeDerives,
+    ) -> Vec<Snippet> {
         composites
             .iter()
             .map(|composite| {
-                let mut code = String::new();
-                let table_name = self.format_name(&composite.name);
-                code.push_str(&format!("pub struct {} {{\n", table_name.to_pascal_case()));
+                let struct_name =
+                    user_types[&(composite.schema.as_str(), composite.name.as_str())].clone();
+                let mut snippet = Snippet {
+                    id: struct_name.to_snake_case(),
+                    imports: Default::default(),
+                    code: String::new(),
+                    exports: vec![],
+                    module_name: None,
+                };
+                let mut body = String::new();
+                let fields = self.composite_fields(composite, user_types);
+                let mut offsets: Vec<usize> = vec![];
 
-                for attr in &composite.attributes {
-                    let rust_type = self.provider.type_name_from(&attr.data_type);
-                    let field_name = attr.name.to_snake_case();
+                for (attr, (field_name, rust_type)) in composite.attributes.iter().zip(&fields) {
+                    snippet.add_type_imports(rust_type);
+                    if self.formatting.annotate_types {
+                        body.push_str(&format!("    /// db type: {}\n", attr.data_type));
+                    }
+                    offsets.push(body.len());
                     let struct_field = format!("    pub {field_name}: {rust_type},\n");
-                    code.push_str(&struct_field);
+                    body.push_str(&struct_field);
                 }
+                self.serde_field_attributes(
+                    &struct_name,
+                    &fields,
+                    &offsets,
+                    type_derives,
+                    &mut body,
+                );
 
-                code.push('}');
-
-                Snippet {
-                    id: table_name,
-                    imports: Default::default(),
-                    code,
+                let mut code = String::new();
+                code.push_str(&self.type_attributes());
+                if let Some(derive) = self.derive_attribute(&struct_name, &fields, type_derives) {
+                    code.push_str(&derive);
                 }
+                if self.formatting.framework == Framework::Sqlx {
+                    code.push_str(&code_for_sqlx_type(&composite.schema, &composite.name));
+                }
+                code.push_str(&format!("pub struct {struct_name} {{\n{body}}}"));
+                snippet.code = code;
+                snippet
             })
             .collect()
     }
 
-    fn code_from_tables(&self, tables: &[database::Table]) -> Vec<Snippet> {
-        tables
+    fn code_from_tables(
+        &self,
+        tables: &[database::Table],
+        user_types: &UserTypes,
+        type_derives: &TypeDerives,
+    ) -> Vec<Snippet> {
+        // names are resolved up front, since typed IDs are referenced by the modules of other tables
+        let table_names: Vec<Option<String>> = tables
             .iter()
-            .map(|table| {
-                let mut code = String::new();
-                let table_name = self.format_name(&table.name);
-                code.push_str(&format!("pub struct {} {{\n", table_name.to_pascal_case()));
+            .map(|table| self.table_name(table, user_types))
+            .collect();
+        let typed_ids = self.typed_ids(tables, &table_names);
+
+        let mut snippets: Vec<Snippet> = tables
+            .iter()
+            .zip(table_names)
+            .filter_map(|(table, table_name)| {
+                let table_name = table_name?;
+                let mut snippet = Snippet {
+                    id: table_name.clone(),
+                    imports: Default::default(),
+                    code: String::new(),
+                    exports: vec![],
+                    module_name: self.table_module_name(table, &table_name),
+                };
+                let typed_id = typed_ids.get(table.name.as_str());
+                let struct_name = table_name.to_pascal_case();
+                let mut body = String::new();
+                let mut fields: Vec<(String, Type)> = vec![];
+                let mut offsets: Vec<usize> = vec![];
+                let mut row_fields = String::new();
+                // the expressions selecting each field and the primary key columns, for the query_as! functions
+                let mut select_columns: Vec<String> = vec![];
+                // the database names of the columns that got a field, for the columns function
+                let mut column_names: Vec<&str> = vec![];
+                let mut primary_keys: Vec<(&database::Column, String, Type)> = vec![];
+                // the column of each field, for the insert, update and delete functions
+                let mut crud_fields: Vec<(&database::Column, String, Type)> = vec![];
+                // the column each field is read from, to detect columns that map to the same field name
+                let mut field_columns: HashMap<String, &str> = HashMap::new();
+                // a single struct level rename replaces the renames of the fields when every column follows the same
+                // casing, such as camelCase
+                let rename_all = match self.formatting.framework == Framework::Sqlx
+                    && !self.formatting.manual_from_row
+                {
+                    true => rename_all_rule(&table.columns),
+                    false => None,
+                };
 
                 for column in &table.columns {
-                    let mut rust_type = self.provider.type_name_from(&column.udt_name);
-                    if column.is_nullable {
+                    let annotations = Annotations::parse(column.comment.as_deref());
+                    if annotations.skip {
+                        continue;
+                    }
+
+                    let mut rust_type = match typed_id_of(table, column, &typed_ids) {
+                        Some(id_type) => id_type,
+                        None => reference_user_types(
+                            self.column_type(&table.name, column),
+                            &column.udt_schema,
+                            user_types,
+                        ),
+                    };
+                    // the nullability of the elements is independent of the column's, e.g. Option<Vec<Option<i32>>>
+                    rust_type = self.with_element_nullability(rust_type);
+                    if self.is_nullable(&table.name, column) {
                         rust_type = Type::Option(Box::new(rust_type))
                     }
-                    let field_name = column.name.to_snake_case();
+                    snippet.add_type_imports(&rust_type);
+                    // the annotated type includes the precision of numerics, which is documented otherwise.
+                    // integer and float columns report a precision as well, only numerics declare one
+                    if self.formatting.annotate_types {
+                        body.push_str(&format!("    /// db type: {}\n", sql_type(column)));
+                    } else if let ("numeric", Some(precision), Some(scale)) = (
+                        column.udt_name.as_str(),
+                        column.numeric_precision,
+                        column.numeric_scale,
+                    ) {
+                        body.push_str(&format!("    /// numeric({precision}, {scale})\n"));
+                    }
+                    if let (true, Some(default)) =
+                        (self.formatting.annotate_defaults, &column.column_default)
+                    {
+                        // a multi line expression would end the doc comment after its first line
+                        let default = default.split_whitespace().collect::<Vec<_>>().join(" ");
+                        body.push_str(&format!("    /// default: {default}\n"));
+                    }
+                    if !column.is_writable {
+                        body.push_str("    /// read only, the role can not update this column\n");
+                    }
+                    let mut field_name = annotations
+                        .rename
+                        .unwrap_or_else(|| field_identifier(&column.name));
+                    if let Some(existing) = field_columns.get(&field_name) {
+                        let base_name = field_name.clone();
+                        let suffix = (2..)
+                            .find(|n| !field_columns.contains_key(&format!("{base_name}_{n}")))
+                            .unwrap_or_default();
+                        field_name = format!("{base_name}_{suffix}");
+                        eprintln!(
+                            "warning: columns `{existing}` and `{}` of table {} both map to the field `{base_name}`, naming the field of `{}` `{field_name}` instead",
+                            column.name, table.name, column.name
+                        );
+                    }
+                    field_columns.insert(field_name.clone(), &column.name);
+                    column_names.push(&column.name);
+                    let is_default = self.formatting.framework == Framework::Sqlx
+                        && self.formatting.sqlx_default_options
+                        && matches!(rust_type, Type::Option(_));
+                    if is_default && !self.formatting.manual_from_row {
+                        body.push_str("    #[sqlx(default)]\n");
+                    }
+                    // the derived FromRow reads the column named like the field unless it is renamed
+                    if self.formatting.framework == Framework::Sqlx
+                        && !self.formatting.manual_from_row
+                        && rename_all.is_none()
+                        && field_name != column.name
+                    {
+                        body.push_str(&format!(
+                            "    #[sqlx(rename = {})]\n",
+                            string_literal(&column.name)
+                        ));
+                    }
+                    row_fields.push_str(&code_for_row_field(&field_name, &column.name, is_default));
+                    select_columns.push(self.select_expression(&table.name, column, &field_name, &rust_type));
+                    if column.is_primary_key {
+                        let key_type = self.column_type(&table.name, column);
+                        let key_type = typed_id_of(table, column, &typed_ids).unwrap_or(key_type);
+                        primary_keys.push((column, field_name.clone(), key_type));
+                    }
+                    offsets.push(body.len());
                     let struct_field = format!("    pub {field_name}: {rust_type},\n");
-                    code.push_str(&struct_field);
+                    body.push_str(&struct_field);
+                    crud_fields.push((column, field_name.clone(), rust_type.clone()));
+                    fields.push((field_name, rust_type));
                 }
+                self.serde_field_attributes(
+                    &struct_name,
+                    &fields,
+                    &offsets,
+                    type_derives,
+                    &mut body,
+                );
 
-                code.push('}');
+                let mut code = String::new();
+                if let Some(typed_id) = typed_id {
+                    code.push_str(&self.code_for_typed_id(
+                        typed_id,
+                        table,
+                        &struct_name,
+                        type_derives,
+                        &mut snippet,
+                    ));
+                }
+                for column in &table.columns {
+                    let qualified_name = format!("{}.{}", table.name, column.name);
+                    let Some(flags) = self.formatting.bitflags.get(&qualified_name) else {
+                        continue;
+                    };
+                    match self.bitflags_type(&table.name, column) {
+                        Some(name) => {
+                            code.push_str(&self.code_for_bitflags(&name, column, flags, &mut snippet))
+                        }
+                        None => eprintln!(
+                            "warning: not generating bitflags for {qualified_name} since its type {} is not an integer",
+                            column.udt_name
+                        ),
+                    }
+                }
+                if table.is_foreign {
+                    code.push_str("/// Read only, the rows of this foreign table are provided by a foreign data wrapper\n");
+                }
+                if table.is_materialized_view {
+                    code.push_str("/// Read only, the rows of this materialized view are the result of its query as of the last refresh\n");
+                }
+                code.push_str(&self.type_attributes());
+                if let Some(derive) = self.derive_attribute(&struct_name, &fields, type_derives) {
+                    code.push_str(&derive);
+                }
+                let manual_from_row =
+                    self.formatting.framework == Framework::Sqlx && self.formatting.manual_from_row;
+                if self.formatting.framework == Framework::Sqlx && !manual_from_row {
+                    code.push_str("#[derive(sqlx::FromRow)]\n");
+                }
+                if let Some(rule) = rename_all {
+                    code.push_str(&format!("#[sqlx(rename_all = \"{rule}\")]\n"));
+                }
+                code.push_str(&format!("pub struct {struct_name} {{\n{body}}}"));
 
-                Snippet {
-                    id: table_name,
-                    imports: Default::default(),
-                    code,
+                if manual_from_row {
+                    for path in ["sqlx::FromRow", "sqlx::Row", "sqlx::postgres::PgRow"] {
+                        snippet.add_import(path);
+                    }
+                    code.push_str(&format!(
+                        "\n\nimpl<'r> FromRow<'r, PgRow> for {struct_name} {{\n    \
+                         fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {{\n        \
+                         Ok(Self {{\n{row_fields}        }})\n    }}\n}}"
+                    ));
                 }
-            })
-            .collect()
-    }
 
-    fn format_name(&self, name: &str) -> String {
-        if self.formatting.singular {
-            name.to_singular()
-        } else {
-            name.to_string()
-        }
+                // foreign tables and materialized views are read only, so there are no rows to construct for inserting
+                let is_read_only = table.is_foreign || table.is_materialized_view;
+                if self.formatting.constructors && !is_read_only {
+                    code.push_str(&code_for_constructor(&struct_name, &fields));
+                }
+
+                if let Some(column_name) = self.formatting.display_fields.get(&table.name) {
+                    match crud_fields
+                        .iter()
+                        .find(|(column, ..)| column.name == *column_name)
+                    {
+                        Some((_, field_name, rust_type)) if rust_type.supports_display() => {
+                            snippet.add_import("std::fmt");
+                            code.push_str(&code_for_display(&struct_name, field_name, rust_type));
+                        }
+                        Some((_, _, rust_type)) => eprintln!(
+                            "warning: not implementing Display for {struct_name} since the type `{rust_type}` of column `{column_name}` doesn't implement it"
+                        ),
+                        None => eprintln!(
+                            "warning: not implementing Display for {struct_name} since table {} has no column `{column_name}`",
+                            table.name
+                        ),
+                    }
+                }
+
+                if self.formatting.column_list {
+                    code.push_str(&code_for_column_list(&struct_name, &column_names));
+                }
+
+                let query_macros =
+                    self.formatting.framework == Framework::Sqlx && self.formatting.query_macros;
+                if query_macros && !select_columns.is_empty() {
+                    let from = qualified_table_name(&table.columns[0].table_schema, &table.name);
+                    let primary_key = match &primary_keys[..] {
+                        [(column, field_name, key_type)] => Some(QueryKey {
+                            column: quoted_identifier(&column.name),
+                            param: field_name.as_str(),
+                            param_type: key_type,
+                            type_override: self.needs_type_override(column, key_type),
+                        }),
+                        _ => None,
+                    };
+                    code.push_str(&code_for_queries(
+                        &struct_name,
+                        &select_columns,
+                        &from,
+                        primary_key,
+                    ));
+                }
+
+                let crud = self.formatting.framework == Framework::Sqlx && self.formatting.crud;
+                if crud && !is_read_only && !crud_fields.is_empty() {
+                    let name = qualified_table_name(&table.columns[0].table_schema, &table.name);
+                    code.push_str(&self.code_for_crud(
+                        &table.name,
+                        &name,
+                        &struct_name,
+                        &crud_fields,
+                        type_derives,
+                        &mut snippet,
+                    ));
+                }
+
+                if table.is_materialized_view && self.formatting.framework == Framework::Sqlx {
+                    let name = qualified_table_name(&table.columns[0].table_schema, &table.name);
+                    code.push_str(&code_for_refresh(&struct_name, &name));
+                }
+
+                snippet.code = code;
+                Some(snippet)
+            })
+            .collect();
+        if self.formatting.phantom_ids && !typed_ids.is_empty() {
+            snippets.push(self.code_for_phantom_id());
+        }
+        snippets
+    }
+
+    /// Returns the typed IDs keyed by the name of the table they identify. Only tables with a single column primary
+    /// key get one, since composite keys can't be represented as a single newtype
+    fn typed_ids<'a>(
+        &self,
+        tables: &'a [database::Table],
+        table_names: &[Option<String>],
+    ) -> HashMap<&'a str, TypedId<'a>> {
+        if !self.formatting.typed_ids {
+            return HashMap::new();
+        }
+
+        tables
+            .iter()
+            .zip(table_names)
+            .filter(|(table, _)| !table.is_foreign)
+            .filter_map(|(table, table_name)| {
+                let table_name = table_name.as_ref()?;
+                let [primary_key] = table
+                    .columns
+                    .iter()
+                    .filter(|column| column.is_primary_key)
+                    .collect::<Vec<_>>()[..]
+                else {
+                    return None;
+                };
+                let typed_id = TypedId {
+                    module: table_name.to_snake_case(),
+                    name: format!("{}Id", table_name.to_singular().to_pascal_case()),
+                    column: primary_key,
+                };
+                Some((table.name.as_str(), typed_id))
+            })
+            .collect()
+    }
+
+    /// Returns the name of the `bitflags` type of a column, e.g. `UserPermissions` for `users.permissions`, or `None`
+    /// if the column has no flags or isn't an integer
+    fn bitflags_type(&self, table_name: &str, column: &database::Column) -> Option<String> {
+        let qualified_name = format!("{table_name}.{}", column.name);
+        if !self.formatting.bitflags.contains_key(&qualified_name)
+            || !matches!(column.udt_name.as_str(), "int2" | "int4" | "int8")
+        {
+            return None;
+        }
+        Some(format!(
+            "{}{}",
+            table_name.to_singular().to_pascal_case(),
+            column.name.to_pascal_case()
+        ))
+    }
+
+    /// Generates the `bitflags` type of an integer column, which sqlx encodes and decodes as the integer it wraps.
+    /// Bits that don't belong to a flag are retained, so decoding never fails
+    fn code_for_bitflags(
+        &self,
+        name: &str,
+        column: &database::Column,
+        flags: &[(String, u64)],
+        snippet: &mut Snippet,
+    ) -> String {
+        let bits = self.provider.type_name_from(&column.udt_name);
+        snippet.exports.push(name.to_string());
+
+        let mut code = String::from("bitflags::bitflags! {\n");
+        for attribute in &self.formatting.struct_attributes {
+            code.push_str(&format!("    {attribute}\n"));
+        }
+        code.push_str("    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]\n");
+        code.push_str(&format!("    pub struct {name}: {bits} {{\n"));
+        for (flag, value) in flags {
+            code.push_str(&format!("        const {flag} = {value};\n"));
+        }
+        code.push_str("    }\n}\n\n");
+
+        if self.formatting.framework == Framework::Sqlx {
+            for path in [
+                "sqlx::Postgres",
+                "sqlx::encode::IsNull",
+                "sqlx::error::BoxDynError",
+                "sqlx::postgres::PgArgumentBuffer",
+                "sqlx::postgres::PgTypeInfo",
+                "sqlx::postgres::PgValueRef",
+            ] {
+                snippet.add_import(path);
+            }
+            code.push_str(&format!(
+                "impl sqlx::Type<Postgres> for {name} {{\n    \
+                 fn type_info() -> PgTypeInfo {{\n        \
+                 <{bits} as sqlx::Type<Postgres>>::type_info()\n    }}\n}}\n\n\
+                 impl<'r> sqlx::Decode<'r, Postgres> for {name} {{\n    \
+                 fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {{\n        \
+                 <{bits} as sqlx::Decode<Postgres>>::decode(value).map(Self::from_bits_retain)\n    }}\n}}\n\n\
+                 impl sqlx::Encode<'_, Postgres> for {name} {{\n    \
+                 fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {{\n        \
+                 <{bits} as sqlx::Encode<Postgres>>::encode(self.bits(), buf)\n    }}\n}}\n\n"
+            ));
+        }
+        code
+    }
+
+    /// Generates the `New` struct of a table, which holds the columns of a row to insert, with a function inserting
+    /// it, and functions updating and deleting the row of the table struct by its primary key. Columns with a default
+    /// are optional in the `New` struct and take their default when they are `None`. The functions return the rows as
+    /// stored, including the values the database generated such as serial keys and defaults
+    fn code_for_crud(
+        &self,
+        table_name: &str,
+        from: &str,
+        struct_name: &str,
+        fields: &[(&database::Column, String, Type)],
+        type_derives: &TypeDerives,
+        snippet: &mut Snippet,
+    ) -> String {
+        let returning = fields
+            .iter()
+            .map(
+                |(column, ..)| match self.query_expression(table_name, column) {
+                    Some(expression) => {
+                        format!("{expression} AS {}", quoted_identifier(&column.name))
+                    }
+                    None => quoted_identifier(&column.name),
+                },
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // identity and generated columns are left to the database, while columns with a default are optional and
+        // only take it when they are None
+        let inserted: Vec<&(&database::Column, String, Type)> = fields
+            .iter()
+            .filter(|(column, ..)| !column.is_generated)
+            .collect();
+        let insert_name = format!("New{struct_name}");
+        let insert_fields: Vec<(String, Type)> = inserted
+            .iter()
+            .map(|(column, field_name, rust_type)| {
+                let rust_type = match (&column.column_default, rust_type) {
+                    (Some(_), Type::Option(_)) | (None, _) => rust_type.clone(),
+                    (Some(_), _) => Type::Option(Box::new(rust_type.clone())),
+                };
+                (field_name.clone(), rust_type)
+            })
+            .collect();
+        snippet.exports.push(insert_name.clone());
+
+        let mut code = format!(
+            "\n\n/// A row of {table_name} to insert, without the columns the database generates\n"
+        );
+        if let Some(derive) = self.derive_attribute(&insert_name, &insert_fields, type_derives) {
+            code.push_str(&derive);
+        }
+        code.push_str(&format!("pub struct {insert_name} {{\n"));
+        for ((column, ..), (field_name, rust_type)) in inserted.iter().zip(&insert_fields) {
+            if let Some(default) = &column.column_default {
+                // a multi line expression would end the doc comment after its first line
+                let default = default.split_whitespace().collect::<Vec<_>>().join(" ");
+                code.push_str(&format!(
+                    "    /// Takes the default of the column, `{default}`, when None\n"
+                ));
+            }
+            code.push_str(&format!("    pub {field_name}: {rust_type},\n"));
+        }
+        code.push('}');
+
+        let insert = match inserted.is_empty() {
+            true => format!("INSERT INTO {from} DEFAULT VALUES RETURNING {returning}"),
+            false => {
+                let columns: Vec<String> = inserted
+                    .iter()
+                    .map(|(column, ..)| quoted_identifier(&column.name))
+                    .collect();
+                let values: Vec<String> = inserted
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (column, ..))| {
+                        let parameter = self.parameter_expression(table_name, column, i + 1);
+                        // COALESCE only evaluates the default when the parameter is NULL, so e.g. a
+                        // sequence is not advanced for rows that provide their own key
+                        match &column.column_default {
+                            Some(default) => format!("COALESCE({parameter}, {default})"),
+                            None => parameter,
+                        }
+                    })
+                    .collect();
+                format!(
+                    "INSERT INTO {from} ({}) VALUES ({}) RETURNING {returning}",
+                    columns.join(", "),
+                    values.join(", ")
+                )
+            }
+        };
+        code.push_str(&format!(
+            "\n\nimpl {insert_name} {{\n    \
+             /// Inserts the row, returning it with the values the database generated\n    \
+             pub async fn insert<'e, E>(&self, executor: E) -> Result<{struct_name}, sqlx::Error>\n    \
+             where\n        E: sqlx::PgExecutor<'e>,\n    {{\n        \
+             sqlx::query_as(r#\"{insert}\"#){}\n            \
+             .fetch_one(executor)\n            \
+             .await\n    }}\n}}",
+            code_for_binds(&inserted)
+        ));
+
+        // rows can only be updated and deleted by their primary key
+        let keys: Vec<&(&database::Column, String, Type)> = fields
+            .iter()
+            .filter(|(column, ..)| column.is_primary_key)
+            .collect();
+        if keys.is_empty() {
+            return code;
+        }
+        let updated: Vec<&(&database::Column, String, Type)> = fields
+            .iter()
+            .filter(|(column, ..)| {
+                !column.is_primary_key && !column.is_generated && column.is_writable
+            })
+            .collect();
+        let condition = |offset: usize| {
+            keys.iter()
+                .enumerate()
+                .map(|(i, (column, ..))| {
+                    format!(
+                        "{} = {}",
+                        quoted_identifier(&column.name),
+                        self.parameter_expression(table_name, column, offset + i + 1)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ")
+        };
+
+        code.push_str(&format!("\n\nimpl {struct_name} {{\n"));
+        if !updated.is_empty() {
+            let assignments: Vec<String> = updated
+                .iter()
+                .enumerate()
+                .map(|(i, (column, ..))| {
+                    format!(
+                        "{} = {}",
+                        quoted_identifier(&column.name),
+                        self.parameter_expression(table_name, column, i + 1)
+                    )
+                })
+                .collect();
+            let update = format!(
+                "UPDATE {from} SET {} WHERE {} RETURNING {returning}",
+                assignments.join(", "),
+                condition(updated.len())
+            );
+            let parameters: Vec<&(&database::Column, String, Type)> =
+                updated.iter().chain(&keys).copied().collect();
+            code.push_str(&format!(
+                "    /// Updates the row with the primary key of this row, returning it as stored\n    \
+                 pub async fn update<'e, E>(&self, executor: E) -> Result<Self, sqlx::Error>\n    \
+                 where\n        E: sqlx::PgExecutor<'e>,\n    {{\n        \
+                 sqlx::query_as(r#\"{update}\"#){}\n            \
+                 .fetch_one(executor)\n            \
+                 .await\n    }}\n\n",
+                code_for_binds(&parameters)
+            ));
+        }
+        let delete = format!("DELETE FROM {from} WHERE {}", condition(0));
+        code.push_str(&format!(
+            "    /// Deletes the row with the primary key of this row\n    \
+             pub async fn delete<'e, E>(&self, executor: E) -> Result<(), sqlx::Error>\n    \
+             where\n        E: sqlx::PgExecutor<'e>,\n    {{\n        \
+             sqlx::query(r#\"{delete}\"#){}\n            \
+             .execute(executor)\n            \
+             .await?;\n        \
+             Ok(())\n    }}\n}}",
+            code_for_binds(&keys)
+        ));
+        code
+    }
+
+    /// Generates the newtype of a typed ID, which sqlx encodes and decodes as the primary key column it wraps. With
+    /// phantom IDs the typed ID is an alias of the generic `Id` instead
+    fn code_for_typed_id(
+        &self,
+        typed_id: &TypedId,
+        table: &database::Table,
+        struct_name: &str,
+        type_derives: &TypeDerives,
+        snippet: &mut Snippet,
+    ) -> String {
+        let rust_type = self.column_type(&table.name, typed_id.column);
+        snippet.add_type_imports(&rust_type);
+        snippet.exports.push(typed_id.name.clone());
+
+        if self.formatting.phantom_ids {
+            snippet.add_import(PHANTOM_ID_PATH);
+            // the key type defaults to the i32 of serial keys
+            let arguments = match rust_type.to_string().as_str() {
+                "i32" => struct_name.to_string(),
+                key_type => format!("{struct_name}, {key_type}"),
+            };
+            return format!(
+                "/// The ID of a row of `{struct_name}`\npub type {} = Id<{arguments}>;\n\n",
+                typed_id.name
+            );
+        }
+
+        let mut code = String::new();
+        let fields = [("0".to_string(), rust_type)];
+        code.push_str(&self.type_attributes());
+        if let Some(derive) = self.derive_attribute(&typed_id.name, &fields, type_derives) {
+            code.push_str(&derive);
+        }
+        let rust_type = &fields[0].1;
+        if self.formatting.framework == Framework::Sqlx {
+            code.push_str("#[derive(sqlx::Type)]\n#[sqlx(transparent)]\n");
+        }
+        code.push_str(&format!(
+            "pub struct {}(pub {rust_type});\n\n",
+            typed_id.name
+        ));
+        code
+    }
+
+    /// Generates the generic `Id` that phantom typed IDs are aliases of. The table it identifies is only a type parameter,
+    /// so the traits are implemented by hand, since deriving them would require the table struct to implement them too
+    fn code_for_phantom_id(&self) -> Snippet {
+        let mut code = String::from(
+            "/// The ID of a row of the table whose struct is `T`, which keeps the IDs of different tables from being mixed\n\
+             /// up while they share their implementations\n",
+        );
+        code.push_str(&self.type_attributes());
+        code.push_str(
+            "pub struct Id<T, K = i32>(pub K, PhantomData<T>);\n\n\
+             impl<T, K> Id<T, K> {\n    \
+             /// Creates the ID of a row of the table whose struct is `T`\n    \
+             pub const fn new(id: K) -> Self {\n        \
+             Self(id, PhantomData)\n    }\n}\n\n\
+             impl<T, K: Clone> Clone for Id<T, K> {\n    \
+             fn clone(&self) -> Self {\n        \
+             Self::new(self.0.clone())\n    }\n}\n\n\
+             impl<T, K: Copy> Copy for Id<T, K> {}\n\n\
+             impl<T, K: PartialEq> PartialEq for Id<T, K> {\n    \
+             fn eq(&self, other: &Self) -> bool {\n        \
+             self.0 == other.0\n    }\n}\n\n\
+             impl<T, K: Eq> Eq for Id<T, K> {}\n\n\
+             impl<T, K: PartialOrd> PartialOrd for Id<T, K> {\n    \
+             fn partial_cmp(&self, other: &Self) -> Option<Ordering> {\n        \
+             self.0.partial_cmp(&other.0)\n    }\n}\n\n\
+             impl<T, K: Ord> Ord for Id<T, K> {\n    \
+             fn cmp(&self, other: &Self) -> Ordering {\n        \
+             self.0.cmp(&other.0)\n    }\n}\n\n\
+             impl<T, K: Hash> Hash for Id<T, K> {\n    \
+             fn hash<H: Hasher>(&self, state: &mut H) {\n        \
+             self.0.hash(state);\n    }\n}\n\n\
+             impl<T, K: fmt::Debug> fmt::Debug for Id<T, K> {\n    \
+             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {\n        \
+             self.0.fmt(f)\n    }\n}",
+        );
+
+        let mut imports = vec![
+            "std::cmp::Ordering",
+            "std::fmt",
+            "std::hash::Hash",
+            "std::hash::Hasher",
+            "std::marker::PhantomData",
+        ];
+        if self.formatting.framework == Framework::Sqlx {
+            imports.extend([
+                "sqlx::Postgres",
+                "sqlx::encode::IsNull",
+                "sqlx::error::BoxDynError",
+                "sqlx::postgres::PgArgumentBuffer",
+                "sqlx::postgres::PgTypeInfo",
+                "sqlx::postgres::PgValueRef",
+            ]);
+            code.push_str(
+                "\n\n\
+                 impl<T, K: sqlx::Type<Postgres>> sqlx::Type<Postgres> for Id<T, K> {\n    \
+                 fn type_info() -> PgTypeInfo {\n        \
+                 K::type_info()\n    }\n\n    \
+                 fn compatible(ty: &PgTypeInfo) -> bool {\n        \
+                 K::compatible(ty)\n    }\n}\n\n\
+                 impl<'r, T, K: sqlx::Decode<'r, Postgres>> sqlx::Decode<'r, Postgres> for Id<T, K> {\n    \
+                 fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {\n        \
+                 K::decode(value).map(Self::new)\n    }\n}\n\n\
+                 impl<'q, T, K: sqlx::Encode<'q, Postgres>> sqlx::Encode<'q, Postgres> for Id<T, K> {\n    \
+                 fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {\n        \
+                 self.0.encode_by_ref(buf)\n    }\n}",
+            );
+        }
+
+        Snippet {
+            id: "Id".to_string(),
+            imports: imports.iter().map(|path| path.to_string()).collect(),
+            code,
+            exports: vec![],
+            module_name: None,
+        }
+    }
+
+    /// Generates the `CiText` newtype that `citext` columns are represented as. Equality and hashing ignore case like
+    /// the database does, so they are implemented by hand instead of derived
+    fn code_for_citext(&self) -> Snippet {
+        let derives: Vec<&str> = self
+            .formatting
+            .derives
+            .iter()
+            .map(String::as_str)
+            .filter(|derive| !["PartialEq", "Eq", "Hash", "PartialOrd", "Ord"].contains(derive))
+            .collect();
+
+        let mut code = String::from(
+            "/// A case insensitive string, which compares equal to strings that only differ in case like `citext` does\n",
+        );
+        code.push_str(&self.type_attributes());
+        if !derives.is_empty() {
+            code.push_str(&format!("#[derive({})]\n", derives.join(", ")));
+        }
+        if self.formatting.framework == Framework::Sqlx {
+            code.push_str("#[derive(sqlx::Type)]\n#[sqlx(transparent)]\n");
+        }
+        code.push_str(
+            "pub struct CiText(pub String);\n\n\
+             impl PartialEq for CiText {\n    \
+             fn eq(&self, other: &Self) -> bool {\n        \
+             self.0.to_lowercase() == other.0.to_lowercase()\n    }\n}\n\n\
+             impl Eq for CiText {}\n\n\
+             impl Hash for CiText {\n    \
+             fn hash<H: Hasher>(&self, state: &mut H) {\n        \
+             self.0.to_lowercase().hash(state);\n    }\n}",
+        );
+
+        Snippet {
+            id: "CiText".to_string(),
+            imports: ["std::hash::Hash", "std::hash::Hasher"]
+                .iter()
+                .map(|path| path.to_string())
+                .collect(),
+            code,
+            exports: vec![],
+            module_name: None,
+        }
+    }
+
+    /// Generates the `Interval` struct that `interval` columns are represented as. With sqlx it is encoded and decoded
+    /// through `PgInterval`, which it converts from and into
+    fn code_for_interval(&self) -> Snippet {
+        let mut code = String::from(
+            "/// An interval of time, decomposed into its months, days and microseconds like Postgres stores it. The parts\n\
+             /// are kept apart since the length of months and days varies\n",
+        );
+        code.push_str(&self.type_attributes());
+        if !self.formatting.derives.is_empty() {
+            code.push_str(&format!(
+                "#[derive({})]\n",
+                self.formatting.derives.join(", ")
+            ));
+        }
+        code.push_str(
+            "pub struct Interval {\n    \
+             pub months: i32,\n    \
+             pub days: i32,\n    \
+             pub microseconds: i64,\n}",
+        );
+
+        let mut imports = vec![];
+        if self.formatting.framework == Framework::Sqlx {
+            imports.extend([
+                "sqlx::Postgres",
+                "sqlx::encode::IsNull",
+                "sqlx::error::BoxDynError",
+                "sqlx::postgres::PgArgumentBuffer",
+                "sqlx::postgres::PgHasArrayType",
+                "sqlx::postgres::PgTypeInfo",
+                "sqlx::postgres::PgValueRef",
+                "sqlx::postgres::types::PgInterval",
+            ]);
+            code.push_str(
+                "\n\n\
+                 impl From<PgInterval> for Interval {\n    \
+                 fn from(interval: PgInterval) -> Self {\n        \
+                 Self {\n            \
+                 months: interval.months,\n            \
+                 days: interval.days,\n            \
+                 microseconds: interval.microseconds,\n        }\n    }\n}\n\n\
+                 impl From<Interval> for PgInterval {\n    \
+                 fn from(interval: Interval) -> Self {\n        \
+                 Self {\n            \
+                 months: interval.months,\n            \
+                 days: interval.days,\n            \
+                 microseconds: interval.microseconds,\n        }\n    }\n}\n\n\
+                 impl sqlx::Type<Postgres> for Interval {\n    \
+                 fn type_info() -> PgTypeInfo {\n        \
+                 PgInterval::type_info()\n    }\n}\n\n\
+                 impl PgHasArrayType for Interval {\n    \
+                 fn array_type_info() -> PgTypeInfo {\n        \
+                 PgInterval::array_type_info()\n    }\n}\n\n\
+                 impl<'r> sqlx::Decode<'r, Postgres> for Interval {\n    \
+                 fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {\n        \
+                 PgInterval::decode(value).map(Self::from)\n    }\n}\n\n\
+                 impl<'q> sqlx::Encode<'q, Postgres> for Interval {\n    \
+                 fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {\n        \
+                 PgInterval {\n            \
+                 months: self.months,\n            \
+                 days: self.days,\n            \
+                 microseconds: self.microseconds,\n        }\n        \
+                 .encode_by_ref(buf)\n    }\n}",
+            );
+        }
+
+        Snippet {
+            id: "Interval".to_string(),
+            imports: imports.iter().map(|path| path.to_string()).collect(),
+            code,
+            exports: vec![],
+            module_name: None,
+        }
+    }
+
+    /// Returns whether the provided trait, e.g. serde's `Deserialize`, is one of the requested derives
+    fn derives(&self, trait_name: &str) -> bool {
+        self.formatting
+            .derives
+            .iter()
+            .any(|derive| derive.rsplit("::").next() == Some(trait_name))
+    }
+
+    /// Marks the fields whose type serde can't handle with `#[serde(skip)]` when a serde trait is derived for the type,
+    /// so the type still compiles. With `serde_null_as_missing`, nullable fields, which are the only ones represented as
+    /// an `Option`, are left out when `None` and default to it when missing. `offsets` are the positions in the body at
+    /// which the declaration of each field starts
+    fn serde_field_attributes(
+        &self,
+        type_name: &str,
+        fields: &[(String, Type)],
+        offsets: &[usize],
+        type_derives: &TypeDerives,
+        body: &mut String,
+    ) {
+        let derives_serde = ["Serialize", "Deserialize"].iter().any(|trait_name| {
+            self.derives(trait_name)
+                && fields
+                    .iter()
+                    .all(|(_, rust_type)| supports_derive(rust_type, trait_name, type_derives))
+        });
+        if !derives_serde {
+            return;
+        }
+
+        let attributes: Vec<(usize, &str)> = fields
+            .iter()
+            .zip(offsets)
+            .filter_map(|((field_name, rust_type), offset)| {
+                if !rust_type.supports_serde() {
+                    eprintln!(
+                        "warning: skipping field `{field_name}` of {type_name} when (de)serializing since serde can't handle its type `{rust_type}`"
+                    );
+                    Some((*offset, "    #[serde(skip)]\n"))
+                } else if self.formatting.serde_null_as_missing
+                    && matches!(rust_type, Type::Option(_))
+                {
+                    Some((
+                        *offset,
+                        "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n",
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // inserting from the back keeps the offsets of the preceding fields valid
+        for (offset, attribute) in attributes.into_iter().rev() {
+            body.insert_str(offset, attribute);
+        }
+    }
+
+    /// Returns the user provided attributes of every generated type, one per line
+    fn type_attributes(&self) -> String {
+        self.formatting
+            .struct_attributes
+            .iter()
+            .map(|attribute| format!("{attribute}\n"))
+            .collect()
+    }
+
+    /// Returns the derive attribute for the requested derives, omitting those that a field's type does not implement
+    fn derive_attribute(
+        &self,
+        type_name: &str,
+        fields: &[(String, Type)],
+        type_derives: &TypeDerives,
+    ) -> Option<String> {
+        let derives = self.supported_derives(fields, type_derives);
+        for derive in &self.formatting.derives {
+            if derives.contains(&derive.as_str()) {
+                continue;
+            }
+            if let Some((field_name, rust_type)) = fields
+                .iter()
+                .find(|(_, rust_type)| !supports_derive(rust_type, derive, type_derives))
+            {
+                eprintln!(
+                    "warning: not deriving {derive} for {type_name} since field `{field_name}` of type `{rust_type}` does not implement it"
+                );
+            }
+        }
+
+        (!derives.is_empty()).then(|| format!("#[derive({})]\n", derives.join(", ")))
+    }
+
+    /// Returns the requested derives that the types of all fields implement, along with `Clone` and `Copy` when they
+    /// are derived whenever possible
+    fn supported_derives(
+        &self,
+        fields: &[(String, Type)],
+        type_derives: &TypeDerives,
+    ) -> Vec<&str> {
+        let mut derives: Vec<&str> = self
+            .formatting
+            .derives
+            .iter()
+            .filter(|derive| {
+                fields
+                    .iter()
+                    .all(|(_, rust_type)| supports_derive(rust_type, derive, type_derives))
+            })
+            .map(String::as_str)
+            .collect();
+
+        let is_copy = fields
+            .iter()
+            .all(|(_, rust_type)| supports_derive(rust_type, "Copy", type_derives));
+        if self.formatting.derive_copy_when_possible && is_copy {
+            // Copy requires Clone to be implemented as well
+            for derive in ["Clone", "Copy"] {
+                if !derives.contains(&derive) {
+                    derives.push(derive);
+                }
+            }
+        }
+        derives
+    }
+
+    /// Returns the derives each enum and composite type of the schema receives, so the types containing them only
+    /// derive what they implement. Composite types can contain each other in any order, so each starts out assumed
+    /// to implement every derive and is narrowed down until none changes anymore
+    fn type_derives(
+        &self,
+        schema: &database::DatabaseSchema,
+        user_types: &UserTypes,
+    ) -> TypeDerives<'_> {
+        let mut type_derives = TypeDerives::new();
+        for e in &schema.enumerations {
+            let path = format!(
+                "super::{}",
+                user_types[&(e.schema.as_str(), e.name.as_str())]
+            );
+            let derives = self.supported_derives(&self.enum_fields(e), &type_derives);
+            type_derives.insert(path, derives);
+        }
+
+        let composites: Vec<(String, Vec<(String, Type)>)> = schema
+            .composite_types
+            .iter()
+            .map(|composite| {
+                let key = (composite.schema.as_str(), composite.name.as_str());
+                let path = format!("super::{}", user_types[&key]);
+                (path, self.composite_fields(composite, user_types))
+            })
+            .collect();
+        loop {
+            let mut changed = false;
+            for (path, fields) in &composites {
+                let derives = self.supported_derives(fields, &type_derives);
+                if type_derives.get(path) != Some(&derives) {
+                    type_derives.insert(path.clone(), derives);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return type_derives;
+            }
+        }
+    }
+
+    /// Returns the fields of the variants of an enum. Fieldless enums support every derivable trait, while the
+    /// unknown variant holds the raw label. Variants of enums with discriminants must be unit variants, so those
+    /// never get one
+    fn enum_fields(&self, e: &database::Enum) -> Vec<(String, Type)> {
+        match self.formatting.enum_unknown_variant && e.backing_type.is_none() {
+            true => vec![("Unknown".to_string(), Type::String("String"))],
+            false => vec![],
+        }
+    }
+
+    /// Returns the name and type of the field of each attribute of a composite type
+    fn composite_fields(
+        &self,
+        composite: &database::CompositeType,
+        user_types: &UserTypes,
+    ) -> Vec<(String, Type)> {
+        composite
+            .attributes
+            .iter()
+            .map(|attr| {
+                let rust_type = self.with_element_nullability(reference_user_types(
+                    self.rust_type_from(&attr.data_type),
+                    &attr.data_type_schema,
+                    user_types,
+                ));
+                (field_identifier(&attr.name), rust_type)
+            })
+            .collect()
+    }
+
+    /// Returns whether a column is represented as an `Option`, taking the forced nullability of columns into account
+    fn is_nullable(&self, table_name: &str, column: &database::Column) -> bool {
+        let qualified_name = format!("{table_name}.{}", column.name);
+        if self.formatting.nullable_columns.contains(&qualified_name) {
+            return true;
+        }
+        if self.formatting.not_null_columns.contains(&qualified_name) {
+            return false;
+        }
+        // primary keys can never be null, even if the information schema reports otherwise
+        column.is_nullable && !column.is_primary_key
+    }
+
+    /// Returns the expression selecting a column in a `query_as!` macro. The alias tells sqlx whether the field is an
+    /// `Option`, since it can't infer the nullability of a cast column, and skips the type check of fields whose type
+    /// differs from the one sqlx infers for the column, such as enums and typed IDs
+    fn select_expression(
+        &self,
+        table_name: &str,
+        column: &database::Column,
+        field_name: &str,
+        rust_type: &Type,
+    ) -> String {
+        let (nullability, inner_type) = match rust_type {
+            Type::Option(inner) => ("?", inner.as_ref()),
+            other => ("!", other),
+        };
+        let expression = self.query_expression(table_name, column);
+        // sqlx infers the converted bits as a Vec<u8>, which fixed size arrays are decoded from as well
+        let type_override = match expression {
+            Some(_) => matches!(inner_type, Type::FixedBytes(_)),
+            None => self.needs_type_override(column, inner_type),
+        };
+        format!(
+            "{} AS \"{field_name}{nullability}{}\"",
+            expression.unwrap_or_else(|| quoted_identifier(&column.name)),
+            if type_override { ": _" } else { "" }
+        )
+    }
+
+    /// Returns whether the type of a field differs from the type sqlx infers for its column
+    fn needs_type_override(&self, column: &database::Column, rust_type: &Type) -> bool {
+        rust_type.to_string() != self.provider.type_name_from(&column.udt_name).to_string()
+    }
+
+    /// Returns the expression a query must select a column with so sqlx can decode it into the type of its field, for
+    /// the representation options that map a column to a type its database type can't be decoded into
+    fn query_expression(&self, table_name: &str, column: &database::Column) -> Option<String> {
+        let qualified_name = format!("{table_name}.{}", column.name);
+        if self.formatting.column_types.contains_key(&qualified_name) {
+            return None;
+        }
+        let name = quoted_identifier(&column.name);
+        match column.udt_name.as_str() {
+            "money" if self.formatting.money_as_decimal => Some(format!("{name}::numeric")),
+            "numeric" if self.formatting.numeric_as_integer && is_integral(column) => {
+                Some(format!("{name}::int8"))
+            }
+            "bit"
+                if self.formatting.compact_flags && column.character_maximum_length == Some(1) =>
+            {
+                Some(format!("{name}::int::bool"))
+            }
+            // bits can't be cast to bytea, but their binary representation is the bytes preceded by the length
+            "bit" if self.formatting.fixed_binary && fixed_binary_length(column).is_some() => {
+                Some(format!("substring(varbit_send({name}) from 5)"))
+            }
+            udt_name if TEXT_CAST_TYPES.contains(&udt_name) => Some(format!("{name}::text")),
+            _ => None,
+        }
+    }
+
+    /// Returns the expression a query passes the field of a column as the parameter with the provided number, which
+    /// converts the representations `query_expression` selects a column as back into the type of the column
+    fn parameter_expression(
+        &self,
+        table_name: &str,
+        column: &database::Column,
+        number: usize,
+    ) -> String {
+        let parameter = format!("${number}");
+        if self.query_expression(table_name, column).is_none() {
+            return parameter;
+        }
+        match column.udt_name.as_str() {
+            "money" => format!("{parameter}::numeric::money"),
+            "bit"
+                if column.character_maximum_length == Some(1) && self.formatting.compact_flags =>
+            {
+                format!("{parameter}::int::bit")
+            }
+            // the bytes are converted to bits through their hexadecimal representation
+            "bit" => format!(
+                "('x' || encode({parameter}, 'hex'))::bit({})",
+                column.character_maximum_length.unwrap_or_default()
+            ),
+            udt_name if TEXT_CAST_TYPES.contains(&udt_name) => format!("{parameter}::{udt_name}"),
+            // integers are converted to the type of the column by the assignment
+            _ => parameter,
+        }
+    }
+
+    /// Represents the elements of an array as `Option` when arrays are configured to contain NULL elements
+    fn with_element_nullability(&self, rust_type: Type) -> Type {
+        match rust_type {
+            Type::Vector(inner) if self.formatting.nullable_array_elements => {
+                Type::Vector(Box::new(Type::Option(inner)))
+            }
+            other => other,
+        }
+    }
+
+    /// Resolves the Rust type of a table column, taking overrides and the column's precision and length into account
+    fn column_type(&self, table_name: &str, column: &database::Column) -> Type {
+        let qualified_name = format!("{table_name}.{}", column.name);
+        if let Some(rust_type) = self.formatting.column_types.get(&qualified_name) {
+            return Type::Custom(self.json_override(column, rust_type));
+        }
+
+        if let Some(name) = self.bitflags_type(table_name, column) {
+            return Type::Custom(name);
+        }
+
+        if self.formatting.numeric_as_integer && column.udt_name == "numeric" && is_integral(column)
+        {
+            return Type::I64("i64");
+        }
+
+        if self.formatting.citext_newtype {
+            match column.udt_name.as_str() {
+                "citext" => return Type::Custom(CITEXT_PATH.to_string()),
+                "_citext" => return Type::Vector(Box::new(Type::Custom(CITEXT_PATH.to_string()))),
+                _ => {}
+            }
+        }
+
+        if self.formatting.interval_struct {
+            match column.udt_name.as_str() {
+                "interval" => return Type::Custom(INTERVAL_PATH.to_string()),
+                "_interval" => {
+                    return Type::Vector(Box::new(Type::Custom(INTERVAL_PATH.to_string())))
+                }
+                _ => {}
+            }
+        }
+
+        if self.formatting.compact_flags && column.character_maximum_length == Some(1) {
+            match column.udt_name.as_str() {
+                "bit" => return Type::Bool("bool"),
+                "bpchar" => return Type::Char("char"),
+                _ => {}
+            }
+        }
+
+        if self.formatting.fixed_binary {
+            if let Some(length) = fixed_binary_length(column) {
+                return Type::FixedBytes(length);
+            }
+        }
+
+        self.rust_type_from(&column.udt_name)
+    }
+
+    /// Wraps the type a `json` or `jsonb` column is overridden with in `sqlx::types::Json` when the framework is sqlx,
+    /// which decodes the column into the type with serde. Overrides that already are a `Json` are kept as they are
+    fn json_override(&self, column: &database::Column, rust_type: &str) -> String {
+        let is_json = matches!(column.udt_name.as_str(), "json" | "jsonb");
+        let path = rust_type.split('<').next().unwrap_or(rust_type).trim();
+        let is_wrapped = path.rsplit("::").next() == Some("Json");
+        match self.formatting.framework {
+            Framework::Sqlx if is_json && !is_wrapped => format!("sqlx::types::Json<{rust_type}>"),
+            _ => rust_type.to_string(),
+        }
+    }
+
+    /// Resolves the Rust type of a database type, applying the representation options
+    fn rust_type_from(&self, db_type: &str) -> Type {
+        self.apply_representation(self.provider.type_name_from(db_type))
+    }
+
+    fn apply_representation(&self, rust_type: Type) -> Type {
+        match rust_type {
+            Type::Vector(inner) => Type::Vector(Box::new(self.apply_representation(*inner))),
+            Type::Option(inner) => Type::Option(Box::new(self.apply_representation(*inner))),
+            // without a framework, types of sqlx are replaced by plain alternatives so the code doesn't depend on it
+            Type::Range(path, inner) => {
+                let path = match self.formatting.framework {
+                    Framework::None => "std::ops::Range",
+                    Framework::Sqlx => path,
+                };
+                Type::Range(path, Box::new(self.apply_representation(*inner)))
+            }
+            Type::Money(_)
+                if self.formatting.money_as_decimal
+                    || self.formatting.framework == Framework::None =>
+            {
+                Type::Decimal("rust_decimal::Decimal")
+            }
+            // an explicitly chosen format is kept without a framework, even if it is a type of sqlx
+            Type::Interval(_) if self.interval_format() == IntervalFormat::Duration => {
+                Type::Interval("chrono::Duration")
+            }
+            Type::U32("sqlx::postgres::types::Oid")
+                if self.formatting.framework == Framework::None =>
+            {
+                Type::U32("u32")
+            }
+            // chrono has no time of day with an offset, so the offset is kept alongside the time
+            Type::TimeTz(_) if self.formatting.framework == Framework::None => {
+                Type::TimeTz("(chrono::NaiveTime, chrono::FixedOffset)")
+            }
+            Type::ByteArray(_) if self.formatting.bytea_format == ByteaFormat::Bytes => {
+                Type::ByteArray("bytes::Bytes")
+            }
+            other => other,
+        }
+    }
+
+    /// Returns the format of interval columns, which defaults to the one of the framework when it isn't set
+    fn interval_format(&self) -> IntervalFormat {
+        self.formatting
+            .interval_format
+            .unwrap_or(match self.formatting.framework {
+                Framework::None => IntervalFormat::Duration,
+                Framework::Sqlx => IntervalFormat::Pg,
+            })
+    }
+
+    /// Returns the names of the Rust types generated for the enums and composite types of the schema, keyed by the
+    /// schema and name of their database type. Types of another schema that share their name with a type of the
+    /// generated schema are prefixed with their schema, e.g. `AuthRole`, since both are written to the same module
+    /// otherwise. Types that collide with the name of a table are suffixed unless tables are renamed
+    fn user_types<'a>(&self, schema: &'a database::DatabaseSchema) -> UserTypes<'a> {
+        let table_names: HashSet<String> = match self.formatting.rename_on_collision {
+            NameCollision::Type => schema
+                .tables
+                .iter()
+                .filter_map(|table| self.base_table_name(table))
+                .map(|table_name| table_name.to_pascal_case())
+                .collect(),
+            NameCollision::Table => HashSet::new(),
+        };
+        let rename = |type_name: String, suffix: &str| {
+            if table_names.contains(&type_name) {
+                eprintln!("warning: renaming type {type_name} to {type_name}{suffix} since a table has the same name");
+                format!("{type_name}{suffix}")
+            } else {
+                type_name
+            }
+        };
+
+        let enums = schema.enumerations.iter().map(|e| {
+            let key = (e.schema.as_str(), e.name.as_str());
+            (key, e.name.to_pascal_case(), "Enum")
+        });
+        let composites = schema.composite_types.iter().map(|composite| {
+            let key = (composite.schema.as_str(), composite.name.as_str());
+            let type_name = self.format_name(&composite.name).to_pascal_case();
+            (key, type_name, "Composite")
+        });
+        let types: Vec<((&str, &str), String, &str)> = enums.chain(composites).collect();
+        // the types of the schema the tables are generated from keep their name
+        let generated_schema = schema
+            .tables
+            .iter()
+            .flat_map(|table| table.columns.first())
+            .map(|column| column.table_schema.as_str())
+            .next()
+            .unwrap_or("public");
+
+        types
+            .iter()
+            .map(|((type_schema, name), type_name, suffix)| {
+                let shared = types.iter().any(|((other_schema, _), other_name, _)| {
+                    other_schema != type_schema && other_name == type_name
+                });
+                let type_name = match shared && *type_schema != generated_schema {
+                    true => {
+                        let prefixed = format!("{}{type_name}", type_schema.to_pascal_case());
+                        eprintln!("warning: renaming type {type_name} of schema {type_schema} to {prefixed} since a type of another schema has the same name");
+                        prefixed
+                    }
+                    false => type_name.clone(),
+                };
+                ((*type_schema, *name), rename(type_name, suffix))
+            })
+            .collect()
+    }
+
+    /// Returns the name the code generated for a table is identified by, or `None` if the table is skipped.
+    /// Tables that collide with the name of an enum or composite type are suffixed if tables are renamed
+    fn table_name(&self, table: &database::Table, user_types: &UserTypes) -> Option<String> {
+        let table_name = self.base_table_name(table)?;
+        let struct_name = table_name.to_pascal_case();
+        let collides = user_types
+            .values()
+            .any(|type_name| *type_name == struct_name);
+        if self.formatting.rename_on_collision == NameCollision::Table && collides {
+            eprintln!("warning: renaming table struct {struct_name} to {struct_name}Row since an enum or composite type has the same name");
+            return Some(format!("{table_name}_row"));
+        }
+        Some(table_name)
+    }
+
+    /// Returns the name of the module of a table when it differs from its struct name, which is the case when only
+    /// one of them is the singular form of the table name. Renamed tables keep the name of their annotation for both
+    fn table_module_name(&self, table: &database::Table, table_name: &str) -> Option<String> {
+        if self.formatting.singular == self.formatting.singular_modules
+            || Annotations::parse(table.comment.as_deref())
+                .rename
+                .is_some()
+        {
+            return None;
+        }
+
+        let mut module_name = match self.formatting.singular_modules {
+            true => table.name.to_singular(),
+            false => table.name.clone(),
+        };
+        // keeps the suffix of a table struct renamed on a collision with an enum or composite type
+        if self.base_table_name(table).as_deref() != Some(table_name) {
+            module_name.push_str("_row");
+        }
+        Some(module_name.to_snake_case())
+    }
+
+    /// Returns the name of a table before collisions are resolved, or `None` if the table is skipped
+    fn base_table_name(&self, table: &database::Table) -> Option<String> {
+        let annotations = Annotations::parse(table.comment.as_deref());
+        if annotations.skip {
+            return None;
+        }
+
+        Some(
+            annotations
+                .rename
+                .unwrap_or_else(|| self.format_name(&table.name)),
+        )
+    }
+
+    fn format_name(&self, name: &str) -> String {
+        if self.formatting.singular {
+            name.to_singular()
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+/// Returns the database type of a column including its declared precision and scale, or its length
+fn sql_type(column: &database::Column) -> String {
+    match (
+        column.udt_name.as_str(),
+        column.numeric_precision,
+        column.numeric_scale,
+        column.character_maximum_length,
+    ) {
+        ("numeric", Some(precision), Some(scale), _) => format!("numeric({precision}, {scale})"),
+        (udt_name, _, _, Some(length)) => format!("{udt_name}({length})"),
+        (udt_name, ..) => udt_name.to_string(),
+    }
+}
+
+/// The system types that sqlx can't decode, which are represented as `String` and selected in their text form
+const TEXT_CAST_TYPES: [&str; 14] = [
+    "regclass",
+    "regcollation",
+    "regconfig",
+    "regdictionary",
+    "regnamespace",
+    "regoper",
+    "regoperator",
+    "regproc",
+    "regprocedure",
+    "regrole",
+    "regtype",
+    "xid",
+    "cid",
+    "tid",
+];
+
+/// The names of the Rust types generated for the enums and composite types of a schema, keyed by the schema and name of
+/// their database type
+type UserTypes<'a> = HashMap<(&'a str, &'a str), String>;
+
+/// The derives each generated enum and composite type received, keyed by the path fields refer to the type by, e.g.
+/// `super::Mood`
+type TypeDerives<'a> = HashMap<String, Vec<&'a str>>;
+
+/// The path that columns represented as the generated `CiText` newtype refer to it by
+const CITEXT_PATH: &str = "super::CiText";
+
+/// The path that columns represented as the generated `Interval` struct refer to it by
+const INTERVAL_PATH: &str = "super::Interval";
+
+/// The path that the aliases of phantom typed IDs refer to the generic `Id` by
+const PHANTOM_ID_PATH: &str = "super::Id";
+
+/// Returns whether any of the tables has a `citext` column
+fn uses_citext(tables: &[database::Table]) -> bool {
+    tables
+        .iter()
+        .flat_map(|table| &table.columns)
+        .any(|column| matches!(column.udt_name.as_str(), "citext" | "_citext"))
+}
+
+/// Returns whether any of the tables has an `interval` column
+fn uses_interval(tables: &[database::Table]) -> bool {
+    tables
+        .iter()
+        .flat_map(|table| &table.columns)
+        .any(|column| matches!(column.udt_name.as_str(), "interval" | "_interval"))
+}
+
+/// The newtype generated for the primary key of a table
+struct TypedId<'a> {
+    /// The module the newtype is defined in, which is the module of its table
+    module: String,
+    name: String,
+    column: &'a database::Column,
+}
+
+/// Returns the typed ID a column is represented as, which is the case for the primary key of a table with a typed ID and
+/// for foreign keys referencing such a primary key. IDs of other tables are referred to by their module path, while
+/// foreign keys into another schema keep their scalar type since that schema's tables are not generated
+fn typed_id_of(
+    table: &database::Table,
+    column: &database::Column,
+    typed_ids: &HashMap<&str, TypedId>,
+) -> Option<Type> {
+    if column.is_primary_key {
+        if let Some(typed_id) = typed_ids.get(table.name.as_str()) {
+            return Some(Type::Custom(typed_id.name.clone()));
+        }
+    }
+
+    let foreign_table = column.foreign_key_table.as_deref()?;
+    if column.foreign_key_schema.as_deref() != Some(column.table_schema.as_str()) {
+        if !typed_ids.is_empty() {
+            eprintln!(
+                "warning: column {}.{} references {}.{foreign_table}, which is in a schema that is not generated, so it keeps its scalar type",
+                table.name,
+                column.name,
+                column.foreign_key_schema.as_deref().unwrap_or_default(),
+            );
+        }
+        return None;
+    }
+    let typed_id = typed_ids.get(foreign_table)?;
+    // a foreign key referencing a unique column other than the primary key, such as an email, holds a value of that
+    // column rather than an ID, so it keeps the type of the column
+    if column.foreign_key_id.as_deref() != Some(typed_id.column.name.as_str()) {
+        return None;
+    }
+    match foreign_table == table.name {
+        true => Some(Type::Custom(typed_id.name.clone())),
+        false => Some(Type::Custom(format!(
+            "super::{}::{}",
+            typed_id.module, typed_id.name
+        ))),
+    }
+}
+
+/// Returns whether a type implements the trait of a derive. Generated enums and composite types implement the derives
+/// they received, while other custom types, such as the ones columns are overridden with, are assumed to implement it
+fn supports_derive(rust_type: &Type, derive: &str, type_derives: &TypeDerives) -> bool {
+    rust_type.supports_derive_with(derive, &|path, derive| {
+        type_derives.get(path).is_none_or(|derives| {
+            derives
+                .iter()
+                .any(|received| received.rsplit("::").next() == Some(derive))
+        })
+    })
+}
+
+/// Returns the name of the custom type that the provided type depends on, if it is not defined in the schema
+fn unknown_type(rust_type: &Type, known: &UserTypes) -> Option<String> {
+    match rust_type {
+        Type::Vector(inner) | Type::Option(inner) | Type::Range(_, inner) => {
+            unknown_type(inner, known)
+        }
+        Type::Custom(name)
+            if !name.contains("::") && !known.keys().any(|(_, type_name)| type_name == name) =>
+        {
+            Some(name.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the number of bytes of a `bit(n)` column whose length is a multiple of 8, which fits into a byte array
+/// exactly
+fn fixed_binary_length(column: &database::Column) -> Option<usize> {
+    match (column.udt_name.as_str(), column.character_maximum_length) {
+        ("bit", Some(length)) if length % 8 == 0 => Some(length as usize / 8),
+        _ => None,
+    }
+}
+
+/// Returns whether the type is, or contains, a custom type
+fn is_custom(rust_type: &Type) -> bool {
+    match rust_type {
+        Type::Vector(inner) | Type::Option(inner) | Type::Range(_, inner) => is_custom(inner),
+        Type::Custom(_) => true,
+        _ => false,
+    }
+}
+
+/// Generates the attributes that allow sqlx to encode and decode a user defined type. Types outside of the
+/// `public` schema are referred to by their schema qualified name, since they are not on the default search path
+fn code_for_sqlx_type(schema: &str, name: &str) -> String {
+    let type_name = string_literal(&sql_type_name(schema, name));
+    format!("#[derive(sqlx::Type)]\n#[sqlx(type_name = {type_name})]\n")
+}
+
+/// Returns the name sqlx resolves a user defined type by, which is qualified with its schema unless it is `public`
+fn sql_type_name(schema: &str, name: &str) -> String {
+    match schema {
+        "public" => quote_identifier(name),
+        _ => format!("{}.{}", quote_identifier(schema), quote_identifier(name)),
+    }
+}
+
+/// Quotes an identifier that PostgreSQL would otherwise fold to lowercase or fail to parse, e.g. a type created
+/// as `"TicketState"`, since sqlx resolves type names by casting them to `regtype`
+fn quote_identifier(identifier: &str) -> String {
+    let is_plain = identifier
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    match is_plain {
+        true => identifier.to_string(),
+        false => format!("\"{}\"", identifier.replace('"', "\"\"")),
+    }
+}
+
+/// Quotes an identifier regardless of whether it needs to be, so columns named after keywords (e.g. `user`) can be
+/// selected in queries
+fn quoted_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Returns the quoted name of a table, qualified with its schema unless it is in the `public` schema
+fn qualified_table_name(schema: &str, name: &str) -> String {
+    match schema {
+        "public" => quoted_identifier(name),
+        _ => format!("{}.{}", quoted_identifier(schema), quoted_identifier(name)),
+    }
+}
+
+/// Returns a Rust string literal containing the provided value
+fn string_literal(value: &str) -> String {
+    format!("{value:?}")
+}
+
+/// Generates a hand written `sqlx::Type` implementation for an enum with an `Unknown(String)` variant, which the
+/// derive can't express. Labels that don't match a variant are decoded into the unknown variant
+fn code_for_sqlx_type_impl(name: &str, e: &database::Enum) -> String {
+    let type_name = string_literal(&sql_type_name(&e.schema, &e.name));
+    let array_type_name = string_literal(&sql_type_name(&e.schema, &format!("_{}", e.name)));
+    let variants: Vec<(String, &str)> = e
+        .values
+        .iter()
+        .map(|value| (value.name.to_pascal_case(), value.name.as_str()))
+        .collect();
+
+    let mut code = format!(
+        "\n\nimpl sqlx::Type<Postgres> for {name} {{\n    \
+         fn type_info() -> PgTypeInfo {{\n        \
+         PgTypeInfo::with_name({type_name})\n    }}\n}}\n\n\
+         impl PgHasArrayType for {name} {{\n    \
+         fn array_type_info() -> PgTypeInfo {{\n        \
+         PgTypeInfo::with_name({array_type_name})\n    }}\n}}\n\n\
+         impl<'r> sqlx::Decode<'r, Postgres> for {name} {{\n    \
+         fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {{\n        \
+         Ok(match <&str as sqlx::Decode<Postgres>>::decode(value)? {{\n"
+    );
+    for (variant, label) in &variants {
+        code.push_str(&format!(
+            "            {} => Self::{variant},\n",
+            string_literal(label)
+        ));
+    }
+    code.push_str(&format!(
+        "            label => Self::Unknown(label.to_string()),\n        }})\n    }}\n}}\n\n\
+         impl sqlx::Encode<'_, Postgres> for {name} {{\n    \
+         fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {{\n        \
+         let label = match self {{\n"
+    ));
+    for (variant, label) in &variants {
+        code.push_str(&format!(
+            "            Self::{variant} => {},\n",
+            string_literal(label)
+        ));
+    }
+    code.push_str(
+        "            Self::Unknown(label) => label.as_str(),\n        };\n        \
+         <&str as sqlx::Encode<Postgres>>::encode(label, buf)\n    }\n}",
+    );
+    code
+}
+
+/// Generates a `TryFrom<&str>` implementation converting the labels of an enum into its variants. Enums with an
+/// unknown variant convert any label, so their conversion can't fail
+fn code_for_try_from(name: &str, e: &database::Enum, unknown_variant: bool) -> String {
+    let error_type = match unknown_variant {
+        true => "std::convert::Infallible",
+        false => "String",
+    };
+    let mut code = format!(
+        "\n\nimpl<'a> TryFrom<&'a str> for {name} {{\n    \
+         type Error = {error_type};\n\n    \
+         fn try_from(value: &'a str) -> Result<Self, Self::Error> {{\n        \
+         match value {{\n"
+    );
+    for value in &e.values {
+        code.push_str(&format!(
+            "            {} => Ok(Self::{}),\n",
+            string_literal(&value.name),
+            value.name.to_pascal_case()
+        ));
+    }
+    match unknown_variant {
+        true => code.push_str("            label => Ok(Self::Unknown(label.to_string())),\n"),
+        false => code.push_str(&format!(
+            "            label => Err(format!(\"unknown {name} label `{{label}}`\")),\n"
+        )),
+    }
+    code.push_str("        }\n    }\n}");
+    code
+}
+
+/// Replaces references to the enums and composite types of the schema with the path of the type generated for them,
+/// which lives in a sibling module. `type_schema` is the schema the database type is defined in, which tells apart
+/// types of different schemas with the same name. Each type is imported once per file, no matter how many fields
+/// refer to it
+fn reference_user_types(rust_type: Type, type_schema: &str, user_types: &UserTypes) -> Type {
+    let reference =
+        |inner: Box<Type>| Box::new(reference_user_types(*inner, type_schema, user_types));
+    match rust_type {
+        Type::Vector(inner) => Type::Vector(reference(inner)),
+        Type::Option(inner) => Type::Option(reference(inner)),
+        Type::Range(path, inner) => Type::Range(path, reference(inner)),
+        Type::Custom(name) => match user_types.get(&(type_schema, name.as_str())) {
+            Some(type_name) => Type::Custom(format!("super::{type_name}")),
+            None => Type::Custom(name),
+        },
+        other => other,
+    }
+}
+
+/// Generates the initialization of a field in a hand written `FromRow` implementation, which reads the column the
+/// field maps to. Default fields are set to `None` when the column is absent from the row
+fn code_for_row_field(field_name: &str, column_name: &str, is_default: bool) -> String {
+    let column_name = string_literal(column_name);
+    if is_default {
+        format!(
+            "            {field_name}: match row.try_get({column_name}) {{\n                \
+             Err(sqlx::Error::ColumnNotFound(_)) => None,\n                \
+             value => value?,\n            }},\n"
+        )
+    } else {
+        format!("            {field_name}: row.try_get({column_name})?,\n")
+    }
+}
+
+/// The keywords of Rust, including reserved ones, that can't be used as the name of a field
+const KEYWORDS: [&str; 50] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while",
+];
+
+/// Turns the name of a column into a valid Rust identifier for its field. Quoted names can contain characters that
+/// can't be part of an identifier, such as spaces and dots, which are replaced by underscores. Names starting with a
+/// digit get a leading underscore and keywords a trailing one, e.g. `first name` becomes `first_name`, `2fa` becomes
+/// `_2fa` and `type` becomes `type_`. Letters of other scripts are kept, since identifiers may contain them
+fn field_identifier(column_name: &str) -> String {
+    let mut identifier = String::new();
+    for c in column_name.to_snake_case().chars() {
+        if c.is_alphabetic() || c.is_ascii_digit() || c == '_' {
+            identifier.push(c);
+        } else if !identifier.ends_with('_') {
+            identifier.push('_');
+        }
+    }
+    let identifier = identifier.trim_end_matches('_');
+    match identifier.chars().next() {
+        None => "column".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{identifier}"),
+        _ if KEYWORDS.contains(&identifier) => format!("{identifier}_"),
+        _ => identifier.to_string(),
+    }
+}
+
+/// Returns the `rename_all` rule of sqlx that turns the name of every field into the name of its column, if the columns
+/// are uniformly named in a casing other than snake_case, e.g. `camelCase` for `userId` and `createdAt`. Tables with
+/// renamed fields or with digits in column names are left to renames of the fields, since the casing of digits is
+/// ambiguous
+fn rename_all_rule(columns: &[database::Column]) -> Option<&'static str> {
+    const RULES: [&str; 4] = [
+        "camelCase",
+        "PascalCase",
+        "SCREAMING_SNAKE_CASE",
+        "kebab-case",
+    ];
+
+    let mut names: Vec<(&str, String)> = vec![];
+    for column in columns {
+        let annotations = Annotations::parse(column.comment.as_deref());
+        if annotations.skip {
+            continue;
+        }
+        let is_plain = column
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || c == '_' || c == '-');
+        if annotations.rename.is_some() || !is_plain {
+            return None;
+        }
+        names.push((&column.name, field_identifier(&column.name)));
+    }
+    if names
+        .iter()
+        .all(|(column_name, field_name)| column_name == field_name)
+    {
+        return None;
+    }
+
+    RULES.into_iter().find(|rule| {
+        names
+            .iter()
+            .all(|(column_name, field_name)| rename_field(rule, field_name) == *column_name)
+    })
+}
+
+/// Returns the name sqlx reads a field from with the provided `rename_all` rule
+fn rename_field(rule: &str, field_name: &str) -> String {
+    match rule {
+        "camelCase" => field_name.to_camel_case(),
+        "PascalCase" => field_name.to_pascal_case(),
+        "SCREAMING_SNAKE_CASE" => field_name.to_uppercase(),
+        "kebab-case" => field_name.replace('_', "-"),
+        _ => field_name.to_string(),
+    }
+}
+
+/// Returns whether a numeric column has a scale of 0 and a precision that fits into an `i64`
+fn is_integral(column: &database::Column) -> bool {
+    // an i64 can represent every value with up to 18 decimal digits
+    const I64_MAX_PRECISION: i32 = 18;
+
+    column.numeric_scale == Some(0)
+        && column
+            .numeric_precision
+            .is_some_and(|p| p <= I64_MAX_PRECISION)
+}
+
+/// The primary key column that a table's rows can be fetched by
+struct QueryKey<'a> {
+    column: String,
+    param: &'a str,
+    param_type: &'a Type,
+    type_override: bool,
+}
+
+/// Generates functions that fetch the rows of a table with the `sqlx::query_as!` macro, which verifies the queries
+/// against the database at compile time. Tables with a single column primary key can also be fetched by it
+fn code_for_queries(
+    struct_name: &str,
+    select_columns: &[String],
+    from: &str,
+    primary_key: Option<QueryKey>,
+) -> String {
+    let select = format!("SELECT {} FROM {from}", select_columns.join(", "));
+    let mut code = format!(
+        "\n\nimpl {struct_name} {{\n    \
+         /// Fetches every row of the table. The query is verified against the database at compile time\n    \
+         pub async fn fetch_all<'e, E>(executor: E) -> Result<Vec<Self>, sqlx::Error>\n    \
+         where\n        E: sqlx::PgExecutor<'e>,\n    {{\n        \
+         sqlx::query_as!(\n            Self,\n            r#\"{select}\"#\n        )\n        \
+         .fetch_all(executor)\n        .await\n    }}\n"
+    );
+
+    if let Some(key) = primary_key {
+        let QueryKey {
+            column,
+            param,
+            param_type,
+            type_override,
+        } = key;
+        // bind parameters of a type sqlx doesn't infer for the column skip its type check
+        let argument = match type_override {
+            true => format!("{param} as _"),
+            false => param.to_string(),
+        };
+        code.push_str(&format!(
+            "\n    /// Fetches the row with the provided primary key, if it exists\n    \
+             pub async fn fetch_by_{param}<'e, E>(executor: E, {param}: {param_type}) -> Result<Option<Self>, sqlx::Error>\n    \
+             where\n        E: sqlx::PgExecutor<'e>,\n    {{\n        \
+             sqlx::query_as!(\n            Self,\n            r#\"{select} WHERE {column} = $1\"#,\n            {argument}\n        )\n        \
+             .fetch_optional(executor)\n        .await\n    }}\n"
+        ));
+    }
+
+    code.push('}');
+    code
+}
+
+/// Generates the calls binding the fields of `self` to the parameters of a query, in the order of the parameters
+fn code_for_binds(parameters: &[&(&database::Column, String, Type)]) -> String {
+    parameters
+        .iter()
+        .map(|(_, field_name, _)| format!("\n            .bind(&self.{field_name})"))
+        .collect()
+}
+
+/// Generates a `columns` function returning the names of the columns the fields are read from, in the order of the
+/// fields, so column lists can be built without repeating the names
+fn code_for_column_list(struct_name: &str, column_names: &[&str]) -> String {
+    let names: Vec<String> = column_names
+        .iter()
+        .map(|name| string_literal(name))
+        .collect();
+    format!(
+        "\n\nimpl {struct_name} {{\n    \
+         /// The names of the columns of the table, in the order of the fields\n    \
+         pub fn columns() -> &'static [&'static str] {{\n        \
+         &[{}]\n    }}\n}}",
+        names.join(", ")
+    )
+}
+
+/// Generates a `refresh` function for a materialized view, which recomputes its rows by running its query again
+fn code_for_refresh(struct_name: &str, view_name: &str) -> String {
+    format!(
+        "\n\nimpl {struct_name} {{\n    \
+         /// Refreshes the materialized view, replacing its rows with the current result of its query\n    \
+         pub async fn refresh<'e, E>(executor: E) -> Result<(), sqlx::Error>\n    \
+         where\n        E: sqlx::PgExecutor<'e>,\n    {{\n        \
+         sqlx::query(r#\"REFRESH MATERIALIZED VIEW {view_name}\"#)\n            \
+         .execute(executor)\n            \
+         .await?;\n        \
+         Ok(())\n    }}\n}}"
+    )
+}
+
+/// Generates a `new` function that takes every required field as a parameter and defaults optional fields to `None`
+fn code_for_constructor(struct_name: &str, fields: &[(String, Type)]) -> String {
+    // clippy's default `too_many_arguments` threshold
+    const MAX_ARGUMENTS: usize = 7;
+
+    let params: Vec<String> = fields
+        .iter()
+        .filter(|(_, rust_type)| !matches!(rust_type, Type::Option(_)))
+        .map(|(name, rust_type)| format!("{name}: {rust_type}"))
+        .collect();
+
+    let mut code = format!("\n\nimpl {struct_name} {{\n");
+    if params.len() > MAX_ARGUMENTS {
+        code.push_str("    #[allow(clippy::too_many_arguments)]\n");
+    }
+    code.push_str(&format!(
+        "    pub fn new({}) -> Self {{\n        Self {{\n",
+        params.join(", ")
+    ));
+
+    for (name, rust_type) in fields {
+        match rust_type {
+            Type::Option(_) => code.push_str(&format!("            {name}: None,\n")),
+            _ => code.push_str(&format!("            {name},\n")),
+        }
+    }
+
+    code.push_str("        }\n    }\n}");
+    code
+}
+
+/// Generates the `Display` implementation of a struct that displays the value of one of its fields. Optional fields are
+/// displayed as nothing when they are `None`
+fn code_for_display(struct_name: &str, field_name: &str, rust_type: &Type) -> String {
+    let body = match rust_type {
+        Type::Option(_) => format!(
+            "match &self.{field_name} {{\n            \
+             Some(value) => write!(f, \"{{value}}\"),\n            \
+             None => Ok(()),\n        }}"
+        ),
+        _ => format!("write!(f, \"{{}}\", self.{field_name})"),
+    };
+    format!(
+        "\n\nimpl fmt::Display for {struct_name} {{\n    \
+         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {{\n        \
+         {body}\n    }}\n}}"
+    )
+}
+
+/**
+Directives embedded in the comment of a table or column that control how it is generated
+
+# Fields
+- `skip`: set by `@autostruct:skip`, omits the object from generation
+- `rename`: set by `@autostruct:rename=<name>`, overrides the generated name of the object
+*/
+#[derive(Default)]
+struct Annotations {
+    skip: bool,
+    rename: Option<String>,
+}
+
+impl Annotations {
+    const SKIP: &'static str = "@autostruct:skip";
+    const RENAME: &'static str = "@autostruct:rename=";
+
+    fn parse(comment: Option<&str>) -> Self {
+        comment.unwrap_or_default().split_whitespace().fold(
+            Annotations::default(),
+            |mut acc, token| {
+                if token == Self::SKIP {
+                    acc.skip = true;
+                } else if let Some(name) = token.strip_prefix(Self::RENAME) {
+                    acc.rename = Some(name.to_string());
+                }
+                acc
+            },
+        )
     }
 }
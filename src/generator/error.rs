@@ -0,0 +1,57 @@
+use std::{error, fmt, time::Duration};
+
+/**
+The kinds of failures that can occur while generating code, which allows consumers of the library to react to
+them individually. The underlying error is preserved and exposed as the source of the failure
+
+# Variants
+- `Connection`: the database could not be connected to
+- `Introspection`: the schema of the database could not be retrieved
+- `Io`: the generated code could not be written to the output directory
+- `Unsupported`: the database the connection string points to is not supported
+- `Timeout`: the run did not finish within the configured maximum runtime
+- `Configuration`: the provided arguments can not be used together
+*/
+#[derive(Debug)]
+pub enum GeneratorError {
+    Connection(anyhow::Error),
+    Introspection(anyhow::Error),
+    Io(anyhow::Error),
+    Unsupported(String),
+    Timeout(Duration),
+    Configuration(String),
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorError::Connection(err) => {
+                write!(f, "failed to connect to the database: {err:#}")
+            }
+            GeneratorError::Introspection(err) => {
+                write!(f, "failed to retrieve the database schema: {err:#}")
+            }
+            GeneratorError::Io(err) => write!(f, "failed to write generated code: {err:#}"),
+            GeneratorError::Unsupported(reason) => write!(f, "unsupported database: {reason}"),
+            GeneratorError::Timeout(max_runtime) => write!(
+                f,
+                "aborted since the run did not finish within the maximum runtime of {}s",
+                max_runtime.as_secs_f64()
+            ),
+            GeneratorError::Configuration(reason) => write!(f, "invalid configuration: {reason}"),
+        }
+    }
+}
+
+impl error::Error for GeneratorError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            GeneratorError::Connection(err)
+            | GeneratorError::Introspection(err)
+            | GeneratorError::Io(err) => Some(err.as_ref()),
+            GeneratorError::Unsupported(_)
+            | GeneratorError::Timeout(_)
+            | GeneratorError::Configuration(_) => None,
+        }
+    }
+}
@@ -3,6 +3,16 @@ The `generator` module contains the code that that is used to generate the Rust
 schema
 */
 mod code;
+mod error;
 mod runner;
 mod utils;
-pub use runner::{run, Arguments};
+pub use code::{
+    ByteaFormat, Entry, Framework, Generator, Indent, IntervalFormat, LineEnding, Mapping,
+    NameCollision, ObjectKind, Options, Snippet,
+};
+pub use error::GeneratorError;
+pub use runner::{
+    list, module_source, parse_ignore_file, prelude_source, run, run_blocking, run_databases,
+    run_profiles, write_all, write_all_async, write_changed, Arguments, DEFAULT_FILE_SUFFIX,
+    FINGERPRINTS_FILE, IGNORE_FILE,
+};
@@ -1,24 +1,57 @@
-use anyhow::{bail, Error};
+use super::GeneratorError;
 
-use crate::database::{
-    self,
-    postgres::{self},
-    Kind, InfoProvider,
-};
+#[cfg(feature = "postgres")]
+use crate::database::postgres::{self};
+use crate::database::{self, InfoProvider, Kind};
 
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(not(feature = "postgres"), allow(unused_variables))]
 pub async fn setup(
     connection_string: &str,
     exclude_tables: Vec<String>,
-) -> Result<impl InfoProvider, Error> {
-    let database: database::Kind = connection_string.try_into()?;
-    let provider = match database {
+    exclude_columns: Vec<String>,
+    include_partitions: bool,
+    include_extensions: bool,
+    enum_tables: Vec<String>,
+    search_path: Option<String>,
+    role: Option<String>,
+    catalog: Option<String>,
+    dialect: Option<Kind>,
+    connection_retries: u32,
+    pg_catalog: bool,
+) -> Result<Box<dyn InfoProvider>, GeneratorError> {
+    let database: database::Kind = match dialect {
+        Some(dialect) => dialect,
+        None => connection_string
+            .try_into()
+            .map_err(|err: anyhow::Error| GeneratorError::Unsupported(err.to_string()))?,
+    };
+    match database {
+        #[cfg(feature = "postgres")]
         Kind::Postgres => {
-            postgres::Builder::new()
+            let provider = postgres::Builder::new()
                 .exclude(exclude_tables)
+                .exclude_columns(exclude_columns)
+                .include_partitions(include_partitions)
+                .include_extensions(include_extensions)
+                .enum_tables(enum_tables)
+                .search_path(search_path)
+                .role(role)
+                .catalog(catalog)
+                .connection_retries(connection_retries)
+                .pg_catalog(pg_catalog)
                 .connect(connection_string)
-                .await?
+                .await
+                .map_err(GeneratorError::Connection)?;
+            Ok(Box::new(provider))
         }
-        _ => bail!("database is not yet supported"),
-    };
-    Ok(provider)
+        #[cfg(not(feature = "postgres"))]
+        Kind::Postgres => Err(GeneratorError::Unsupported(format!(
+            "autostruct was built without the `{}` feature",
+            database.feature()
+        ))),
+        _ => Err(GeneratorError::Unsupported(
+            "database is not yet supported".to_string(),
+        )),
+    }
 }
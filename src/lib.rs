@@ -1,3 +1,3 @@
-mod database;
+pub mod database;
 pub mod generator;
-mod rust;
+pub mod rust;
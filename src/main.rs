@@ -14,9 +14,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Generate(args) => {
-            let args: generator::Arguments = args.try_into()?;
-            generator::run(args).await?;
+        Commands::Generate(args) => match args.profiles()? {
+            Some(profiles) => generator::run_profiles(profiles).await?,
+            None => {
+                let databases: Vec<generator::Arguments> = args.try_into()?;
+                match <[generator::Arguments; 1]>::try_from(databases) {
+                    Ok([args]) => generator::run(args).await?,
+                    Err(databases) => generator::run_databases(databases).await?,
+                }
+            }
+        },
+        Commands::List(args) => {
+            let databases: Vec<generator::Arguments> = args.try_into()?;
+            for args in databases {
+                generator::list(args).await?;
+            }
         }
     };
 
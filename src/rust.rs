@@ -1,18 +1,23 @@
 // An enum to represent Rust types
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Type {
     Bit(&'static str),
     Bool(&'static str),
     I8(&'static str),
+    Char(&'static str),
     I16(&'static str),
     I32(&'static str),
     I64(&'static str),
+    U8(&'static str),
+    U16(&'static str),
     U32(&'static str),
+    U64(&'static str),
     F32(&'static str),
     F64(&'static str),
     Uuid(&'static str),
     Date(&'static str),
     Time(&'static str),
+    TimeTz(&'static str),
     Timestamp(&'static str),
     TimestampWithTz(&'static str),
     Decimal(&'static str),
@@ -23,33 +28,39 @@ pub enum Type {
     ByteArray(&'static str),
     Unit(&'static str),
     Interval(&'static str),
-    Range(Box<Type>),
+    Range(&'static str, Box<Type>),
     Money(&'static str),
     Tree(&'static str),
     Query(&'static str),
     Void(&'static str),
+    FixedBytes(usize),
     Option(Box<Type>),
     Vector(Box<Type>),
     Custom(String),
 }
 
-// Implement display for a rust:Type to visualize the mapping
-impl std::fmt::Display for Type {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Type {
+    /// Returns the name of a basic type, or `None` for container types that wrap other types and byte arrays,
+    /// whose name depends on their length
+    fn name(&self) -> Option<&str> {
         match self {
-            // Basic types with static str names
             Type::Bit(name) |
             Type::Bool(name) |
             Type::I8(name) |
+            Type::Char(name) |
             Type::I16(name) |
             Type::I32(name) |
             Type::I64(name) |
+            Type::U8(name) |
+            Type::U16(name) |
             Type::U32(name) |
+            Type::U64(name) |
             Type::F32(name) |
             Type::F64(name) |
             Type::Uuid(name) |
             Type::Date(name) |
             Type::Time(name) |
+            Type::TimeTz(name) |
             Type::Timestamp(name) |
             Type::TimestampWithTz(name) |
             Type::Decimal(name) |
@@ -63,15 +74,206 @@ impl std::fmt::Display for Type {
             Type::Money(name) |
             Type::Tree(name) |
             Type::Query(name) |
-            Type::Void(name) => write!(f, "{name}"),
+            Type::Void(name) => Some(name),
+            Type::Custom(name) => Some(name),
+            Type::Range(..) | Type::Option(_) | Type::Vector(_) | Type::FixedBytes(_) => None,
+        }
+    }
+
+    /// Returns whether the type implements the trait of the provided derive macro.
+    /// Traits not known to be unsupported by a type are assumed to be implemented
+    pub fn supports_derive(&self, derive: &str) -> bool {
+        self.supports_derive_with(derive, &|_, _| true)
+    }
+
+    /// Returns whether the type implements the trait of the provided derive macro, asking `custom` with the path of
+    /// each custom type and the unqualified derive, e.g. for generated types that didn't receive every derive
+    pub fn supports_derive_with(&self, derive: &str, custom: &dyn Fn(&str, &str) -> bool) -> bool {
+        // derives may be qualified, e.g. `serde::Serialize`
+        let derive = derive.rsplit("::").next().unwrap_or(derive);
+        match (self, derive) {
+            // fields serde can't handle are skipped, which requires them to implement Default when
+            // deserializing
+            (_, "Serialize") if !self.supports_serde() => true,
+            (_, "Deserialize") if !self.supports_serde() => self.is_default(),
+            // heap allocated containers can never be copied
+            (Type::Vector(_) | Type::Range(..), "Copy") => false,
+            // neither std's nor sqlx's ranges are ordered
+            (Type::Range(..), "PartialOrd" | "Ord") => false,
+            // sqlx's ranges can't be hashed and have no default, unlike std's
+            (Type::Range(path, inner), "Hash" | "Default") => {
+                *path != "sqlx::postgres::types::PgRange"
+                    && inner.supports_derive_with(derive, custom)
+            }
+            // vectors and options default to being empty, whatever their elements are
+            (Type::Vector(_) | Type::Option(_), "Default") => true,
+            (Type::Vector(inner) | Type::Option(inner) | Type::Range(_, inner), _) => {
+                inner.supports_derive_with(derive, custom)
+            }
+            // floating point numbers and json values only implement partial equality
+            (Type::F32(_) | Type::F64(_) | Type::Json(_), "Eq" | "Hash") => false,
+            (Type::TimeTz(name), "Eq" | "Hash") => *name != "sqlx::postgres::types::PgTimeTz",
+            // floating point numbers are only partially ordered
+            (Type::F32(_) | Type::F64(_), "Ord") => false,
+            // json values, times with an offset and sqlx's Postgres specific types have no
+            // order at all
+            (
+                Type::Json(_) | Type::TimeTz(_) | Type::Tree(_) | Type::Query(_),
+                "PartialOrd" | "Ord",
+            ) => false,
+            (Type::Interval(name), "PartialOrd" | "Ord") => {
+                *name != "sqlx::postgres::types::PgInterval"
+            }
+            (Type::Money(name), "PartialOrd" | "Ord" | "Hash") => {
+                *name != "sqlx::postgres::types::PgMoney"
+            }
+            // neither sqlx's time with an offset nor chrono's offset have a default
+            (Type::TimeTz(_), "Default") => false,
+            (Type::U32(name), "PartialOrd" | "Ord") => *name != "sqlx::postgres::types::Oid",
+            // the standard library only implements Default for arrays of up to 32 elements
+            (Type::FixedBytes(length), "Default") => *length <= 32,
+            (_, "Copy") => self.is_copy(),
+            (Type::Custom(path), _) => custom(path, derive),
+            _ => true,
+        }
+    }
+
+    /// Returns whether serde can serialize and deserialize the type. sqlx's Postgres specific types and
+    /// `chrono::Duration` don't implement its traits
+    pub fn supports_serde(&self) -> bool {
+        match self {
+            Type::Vector(inner) | Type::Option(inner) => inner.supports_serde(),
+            Type::Range(path, inner) => *path == "std::ops::Range" && inner.supports_serde(),
+            Type::Bit(_) | Type::Interval(_) | Type::Tree(_) | Type::Query(_) => false,
+            Type::Money(name) => *name != "sqlx::postgres::types::PgMoney",
+            Type::TimeTz(name) => *name != "sqlx::postgres::types::PgTimeTz",
+            Type::FixedBytes(length) => *length <= 32,
+            _ => true,
+        }
+    }
+
+    /// Returns whether the type implements `Display`, which optional types do when their value does.
+    /// Types not known to lack it are assumed to implement it
+    pub fn supports_display(&self) -> bool {
+        match self {
+            Type::Option(inner) => inner.supports_display(),
+            Type::Vector(_) |
+            Type::Range(..) |
+            Type::ByteArray(_) |
+            Type::FixedBytes(_) |
+            Type::Bit(_) |
+            Type::TimeTz(_) |
+            Type::Money(_) |
+            Type::Unit(_) |
+            Type::Void(_) => false,
+            Type::Interval(name) => *name == "chrono::Duration",
+            _ => true,
+        }
+    }
+
+    // Returns whether a type that serde can't handle implements `Default`
+    fn is_default(&self) -> bool {
+        match self {
+            Type::Vector(_) | Type::Option(_) | Type::Money(_) => true,
+            Type::Interval(name) => *name == "sqlx::postgres::types::PgInterval",
+            _ => false,
+        }
+    }
 
+    // Returns whether a basic type implements `Copy`
+    fn is_copy(&self) -> bool {
+        match self {
+            Type::Bool(_) |
+            Type::I8(_) |
+            Type::Char(_) |
+            Type::I16(_) |
+            Type::I32(_) |
+            Type::I64(_) |
+            Type::U8(_) |
+            Type::U16(_) |
+            Type::U32(_) |
+            Type::U64(_) |
+            Type::F32(_) |
+            Type::F64(_) |
+            Type::Uuid(_) |
+            Type::Date(_) |
+            Type::Time(_) |
+            Type::TimeTz(_) |
+            Type::Timestamp(_) |
+            Type::TimestampWithTz(_) |
+            Type::Decimal(_) |
+            Type::IpNetwork(_) |
+            Type::Money(_) |
+            Type::FixedBytes(_) |
+            Type::Unit(_) => true,
+            // PgInterval is not Copy, but chrono::Duration is
+            Type::Interval(name) => *name == "chrono::Duration",
+            _ => false,
+        }
+    }
+
+    /// Returns the fully qualified paths (e.g. `chrono::NaiveDate`) that must be imported for the type to resolve
+    pub fn imports(&self) -> Vec<String> {
+        match self {
+            Type::Vector(inner) | Type::Option(inner) => inner.imports(),
+            Type::Range(path, inner) => {
+                let mut imports = vec![path.to_string()];
+                imports.append(&mut inner.imports());
+                imports
+            }
+            other => other
+                .name()
+                .map(|name| {
+                    path_segments(name)
+                        .filter(|p| p.contains("::"))
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// Implement display for a rust:Type to visualize the mapping.
+// Paths are rendered unqualified since the generator emits the matching imports
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
             // Container types that wrap other types
             Type::Vector(inner) => write!(f, "Vec<{inner}>"),
             Type::Option(inner) => write!(f, "Option<{inner}>"),
-            Type::Range(inner) => write!(f, "Range<{inner}>"),
+            Type::Range(path, inner) => write!(f, "{}<{inner}>", unqualified(path)),
+            Type::FixedBytes(length) => write!(f, "[u8; {length}]"),
+
+            // Basic and custom types
+            other => write!(f, "{}", unqualified(other.name().unwrap_or_default())),
+        }
+    }
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == ':'
+}
+
+// Splits a type name such as `chrono::DateTime<chrono::Utc>` into the paths it is composed of
+fn path_segments(name: &str) -> impl Iterator<Item = &str> {
+    name.split(|c: char| !is_path_char(c)).filter(|p| !p.is_empty())
+}
 
-            // Custom type that owns a String
-            Type::Custom(name) => write!(f, "{name}"),
+// Strips the module prefix from every path in a type name, e.g. `chrono::DateTime<chrono::Utc>`
+// becomes `DateTime<Utc>`
+fn unqualified(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut path = String::new();
+    for c in name.chars() {
+        if is_path_char(c) {
+            path.push(c);
+            continue;
         }
+        result.push_str(path.rsplit("::").next().unwrap_or_default());
+        path.clear();
+        result.push(c);
     }
+    result.push_str(path.rsplit("::").next().unwrap_or_default());
+    result
 }
@@ -0,0 +1,1415 @@
+/*!
+Golden file tests that generate code from a constructed `DatabaseSchema` and compare the result against the
+snapshots committed in `tests/golden/snapshots`. These do not require a database, which allows type mapping
+and formatting changes to be verified without Docker.
+
+Set the `AUTOSTRUCT_UPDATE_GOLDEN` environment variable to (re)write the snapshots after an intended change.
+*/
+
+use std::{env, fs, path::PathBuf};
+
+use anyhow::{bail, Context, Error};
+use async_trait::async_trait;
+use autostruct::{
+    database::{
+        postgres, Attribute, Column, CompositeType, DatabaseSchema, Enum, EnumValue, InfoProvider,
+        Table,
+    },
+    generator::{
+        module_source, parse_ignore_file, prelude_source, write_all, write_changed, Framework,
+        Generator, Indent, IntervalFormat, LineEnding, NameCollision, Options, DEFAULT_FILE_SUFFIX,
+    },
+    rust,
+};
+
+/// An `InfoProvider` that maps types like PostgreSQL but never connects to a database
+struct PostgresTypes;
+
+#[async_trait]
+impl InfoProvider for PostgresTypes {
+    fn type_name_from(&self, db_type: &str) -> rust::Type {
+        postgres::type_name_from(db_type)
+    }
+
+    async fn get_schema(&self) -> Result<DatabaseSchema, Error> {
+        bail!("golden tests generate code from a constructed schema")
+    }
+}
+
+/// Generates code for the schema and compares it against the snapshot with the provided name
+pub fn assert_golden(name: &str, options: Options, schema: &DatabaseSchema) -> Result<(), Error> {
+    let generator = Generator::new(options, Box::new(PostgresTypes));
+    let actual = generator
+        .generate_code_from(schema)
+        .iter()
+        .map(|snippet| format!("// {}\n{}\n", snippet.id, snippet.finalize()))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    assert_snapshot(name, &actual)
+}
+
+/// Compares the provided source code against the snapshot with the provided name
+pub fn assert_snapshot(name: &str, actual: &str) -> Result<(), Error> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/snapshots")
+        .join(format!("{name}.snap"));
+
+    if env::var_os("AUTOSTRUCT_UPDATE_GOLDEN").is_some() {
+        fs::write(&path, actual).context("failed to write golden file")?;
+        return Ok(());
+    }
+
+    let expected = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read golden file {}", path.display()))?;
+    if expected != actual {
+        bail!(
+            "generated code does not match golden file {}\n\n--- expected\n{expected}\n--- actual\n{actual}",
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn column(name: &str, udt_name: &str, is_nullable: bool) -> Column {
+    Column {
+        name: name.to_string(),
+        udt_name: udt_name.to_string(),
+        udt_schema: "public".to_string(),
+        data_type: udt_name.to_string(),
+        numeric_precision: None,
+        numeric_scale: None,
+        character_maximum_length: None,
+        is_nullable,
+        column_default: None,
+        is_generated: false,
+        is_unique: false,
+        is_primary_key: false,
+        foreign_key_schema: None,
+        foreign_key_table: None,
+        foreign_key_id: None,
+        table_schema: "public".to_string(),
+        is_writable: true,
+        comment: None,
+    }
+}
+
+pub fn primary_key(name: &str, udt_name: &str) -> Column {
+    Column {
+        is_primary_key: true,
+        ..column(name, udt_name, false)
+    }
+}
+
+pub fn table(name: &str, columns: Vec<Column>) -> Table {
+    Table {
+        name: name.to_string(),
+        comment: None,
+        columns,
+        is_foreign: false,
+        is_materialized_view: false,
+    }
+}
+
+/// A schema covering the commonly used column types
+pub fn basic_schema() -> DatabaseSchema {
+    DatabaseSchema {
+        enumerations: vec![Enum {
+            schema: "public".to_string(),
+            name: "mood".to_string(),
+            values: ["sad", "ok", "happy"]
+                .iter()
+                .enumerate()
+                .map(|(i, value)| EnumValue {
+                    name: value.to_string(),
+                    order: i as f32,
+                    discriminant: None,
+                })
+                .collect(),
+            backing_type: None,
+        }],
+        composite_types: vec![CompositeType {
+            schema: "public".to_string(),
+            name: "address".to_string(),
+            attributes: vec![
+                Attribute {
+                    name: "street".to_string(),
+                    data_type: "varchar".to_string(),
+                    data_type_schema: "pg_catalog".to_string(),
+                },
+                Attribute {
+                    name: "zip_code".to_string(),
+                    data_type: "varchar".to_string(),
+                    data_type_schema: "pg_catalog".to_string(),
+                },
+            ],
+        }],
+        tables: vec![
+            table(
+                "users",
+                vec![
+                    primary_key("id", "uuid"),
+                    column("name", "varchar", false),
+                    column("email", "citext", true),
+                    column("created_at", "timestamptz", false),
+                    column("birthday", "date", true),
+                    column("settings", "jsonb", false),
+                    column("mood", "mood", true),
+                    column("previous_moods", "_mood", false),
+                    column("home", "address", true),
+                ],
+            ),
+            table(
+                "measurements",
+                vec![
+                    primary_key("id", "int8"),
+                    column("value", "float8", false),
+                    column("price", "numeric", true),
+                    column("tags", "_text", false),
+                    column("payload", "bytea", true),
+                ],
+            ),
+        ],
+    }
+}
+
+pub fn test_default_options() -> Result<(), Error> {
+    assert_golden("default_options", Options::default(), &basic_schema())
+}
+
+pub fn test_derives_and_constructors() -> Result<(), Error> {
+    let options = Options {
+        derives: ["Debug", "Clone", "PartialEq", "Eq"]
+            .iter()
+            .map(|d| d.to_string())
+            .collect(),
+        derive_copy_when_possible: true,
+        constructors: true,
+        ..Default::default()
+    };
+    assert_golden("derives_and_constructors", options, &basic_schema())
+}
+
+pub fn test_module_source() -> Result<(), Error> {
+    let generator = Generator::new(Options::default(), Box::new(PostgresTypes));
+    let snippets = generator.generate_code_from(&basic_schema());
+    assert_snapshot(
+        "module_source",
+        &module_source(&snippets, DEFAULT_FILE_SUFFIX),
+    )
+}
+
+pub fn test_empty_schema() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        tables: vec![],
+        enumerations: vec![],
+        composite_types: vec![],
+    };
+    assert_golden("empty_schema", Options::default(), &schema)?;
+    assert_snapshot(
+        "empty_module_source",
+        &module_source(&[], DEFAULT_FILE_SUFFIX),
+    )
+}
+
+pub fn test_list_entries() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.push(table(
+        "places",
+        vec![
+            primary_key("id", "int4"),
+            column("location", "geometry", false),
+            column("area", "_geometry", true),
+        ],
+    ));
+
+    let generator = Generator::new(Options::default(), Box::new(PostgresTypes));
+    let listing = generator
+        .list_entries_from(&schema)
+        .iter()
+        .map(|entry| format!("{entry}\n"))
+        .collect::<String>();
+    assert_snapshot("list_entries", &listing)
+}
+
+pub fn test_sqlx_framework() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.enumerations.push(Enum {
+        schema: "auth".to_string(),
+        name: "role".to_string(),
+        values: ["admin", "read_only"]
+            .iter()
+            .enumerate()
+            .map(|(i, value)| EnumValue {
+                name: value.to_string(),
+                order: i as f32,
+                discriminant: None,
+            })
+            .collect(),
+        backing_type: None,
+    });
+
+    let options = Options {
+        framework: Framework::Sqlx,
+        ..Default::default()
+    };
+    assert_golden("sqlx_framework", options, &schema)
+}
+
+pub fn test_manual_from_row() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.retain(|table| table.name == "users");
+    schema.tables[0].columns[2].comment = Some("@autostruct:rename=email_address".to_string());
+    schema.tables[0].columns[3].is_writable = false;
+
+    let options = Options {
+        framework: Framework::Sqlx,
+        sqlx_default_options: true,
+        manual_from_row: true,
+        ..Default::default()
+    };
+    assert_golden("manual_from_row", options, &schema)
+}
+
+pub fn test_column_overrides() -> Result<(), Error> {
+    let options = Options {
+        nullable_columns: ["measurements.value".to_string()].into_iter().collect(),
+        not_null_columns: ["users.birthday".to_string()].into_iter().collect(),
+        column_types: [
+            ("users.email", "crate::domain::Email"),
+            (
+                "users.settings",
+                "sqlx::types::Json<crate::domain::Settings>",
+            ),
+            ("measurements.id", "i64"),
+        ]
+        .iter()
+        .map(|(column, rust_type)| (column.to_string(), rust_type.to_string()))
+        .collect(),
+        ..Default::default()
+    };
+    assert_golden("column_overrides", options, &basic_schema())
+}
+
+pub fn test_json_column_override() -> Result<(), Error> {
+    let options = Options {
+        framework: Framework::Sqlx,
+        column_types: [
+            ("users.settings", "crate::domain::Settings"),
+            ("users.email", "crate::domain::Email"),
+        ]
+        .iter()
+        .map(|(column, rust_type)| (column.to_string(), rust_type.to_string()))
+        .collect(),
+        ..Default::default()
+    };
+    assert_golden("json_column_override", options, &basic_schema())
+}
+
+pub fn test_bitflags() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "users",
+            vec![
+                column("name", "text", false),
+                column("permissions", "int4", false),
+                column("email", "text", true),
+            ],
+        )],
+    };
+    let options = Options::default()
+        .framework(Framework::Sqlx)
+        .bitflags(
+            "users.permissions",
+            [("READ", 1), ("WRITE", 2), ("ADMIN", 4)],
+        )
+        .bitflags("users.email", [("VERIFIED", 1)]);
+    assert_golden("bitflags", options, &schema)
+}
+
+/// A schema with a table and an enum that are both named `status`
+fn colliding_schema() -> DatabaseSchema {
+    let mut schema = basic_schema();
+    schema.enumerations.push(Enum {
+        schema: "public".to_string(),
+        name: "status".to_string(),
+        values: vec![EnumValue {
+            name: "active".to_string(),
+            order: 1.0,
+            discriminant: None,
+        }],
+        backing_type: None,
+    });
+    schema.tables = vec![table(
+        "status",
+        vec![
+            primary_key("id", "int4"),
+            column("current", "status", false),
+        ],
+    )];
+    schema
+}
+
+pub fn test_rename_type_on_collision() -> Result<(), Error> {
+    let schema = colliding_schema();
+    let generator = Generator::new(Options::default(), Box::new(PostgresTypes));
+    assert_snapshot(
+        "rename_type_on_collision_module",
+        &module_source(&generator.generate_code_from(&schema), DEFAULT_FILE_SUFFIX),
+    )?;
+    assert_golden("rename_type_on_collision", Options::default(), &schema)
+}
+
+pub fn test_rename_table_on_collision() -> Result<(), Error> {
+    let options = Options {
+        rename_on_collision: NameCollision::Table,
+        ..Default::default()
+    };
+    assert_golden("rename_table_on_collision", options, &colliding_schema())
+}
+
+pub fn test_tab_indent() -> Result<(), Error> {
+    let options = Options {
+        indent: Indent::Tabs,
+        constructors: true,
+        ..Default::default()
+    };
+    assert_golden("tab_indent", options, &basic_schema())
+}
+
+pub fn test_crlf_line_endings() -> Result<(), Error> {
+    let source = LineEnding::Crlf.apply(&module_source(&[], DEFAULT_FILE_SUFFIX));
+    let lines: Vec<&str> = source.split_terminator('\n').collect();
+    if lines.is_empty() || lines.iter().any(|line| !line.ends_with('\r')) {
+        bail!("expected every line to end with \\r\\n but got {source:?}");
+    }
+    Ok(())
+}
+
+pub fn test_enum_from_table() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.enumerations.push(Enum {
+        schema: "public".to_string(),
+        name: "status".to_string(),
+        values: [(1, "pending"), (2, "shipped"), (5, "cancelled")]
+            .iter()
+            .enumerate()
+            .map(|(i, (discriminant, value))| EnumValue {
+                name: value.to_string(),
+                order: i as f32,
+                discriminant: Some(*discriminant),
+            })
+            .collect(),
+        backing_type: Some("int4".to_string()),
+    });
+    schema.tables = vec![table(
+        "orders",
+        vec![primary_key("id", "int8"), column("status", "status", false)],
+    )];
+
+    let options = Options {
+        framework: Framework::Sqlx,
+        ..Default::default()
+    };
+    assert_golden("enum_from_table", options, &schema)
+}
+
+pub fn test_write_all() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.clear();
+    let generator = Generator::new(Options::default(), Box::new(PostgresTypes));
+
+    let mut output: Vec<u8> = vec![];
+    write_all(&generator.generate_code_from(&schema), &mut output)?;
+    assert_snapshot("write_all", &String::from_utf8(output)?)
+}
+
+pub fn test_non_exhaustive_enums() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.clear();
+    let options = Options {
+        derives: vec!["Debug".to_string(), "serde::Deserialize".to_string()],
+        non_exhaustive_enums: true,
+        ..Default::default()
+    };
+    assert_golden("non_exhaustive_enums", options, &schema)
+}
+
+/// sqlx can't decode unknown labels into a `#[serde(other)]` variant, so enums generated for it are only marked
+/// `#[non_exhaustive]`
+pub fn test_non_exhaustive_sqlx_enums() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.clear();
+    let options = Options {
+        framework: Framework::Sqlx,
+        derives: vec!["Debug".to_string(), "serde::Deserialize".to_string()],
+        non_exhaustive_enums: true,
+        ..Default::default()
+    };
+    assert_golden("non_exhaustive_sqlx_enums", options, &schema)
+}
+
+pub fn test_enum_unknown_variant() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.clear();
+    schema.composite_types.clear();
+    let options = Options {
+        framework: Framework::Sqlx,
+        derives: vec!["Debug".to_string(), "Clone".to_string()],
+        derive_copy_when_possible: true,
+        enum_unknown_variant: true,
+        ..Default::default()
+    };
+    assert_golden("enum_unknown_variant", options, &schema)
+}
+
+/// Returns a column referencing the column of another table
+pub fn foreign_key(name: &str, udt_name: &str, table: &str, column_name: &str) -> Column {
+    Column {
+        foreign_key_schema: Some("public".to_string()),
+        foreign_key_table: Some(table.to_string()),
+        foreign_key_id: Some(column_name.to_string()),
+        ..column(name, udt_name, false)
+    }
+}
+
+/// A schema with tables that get typed IDs, which are referenced by foreign keys
+fn typed_ids_schema() -> DatabaseSchema {
+    DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![
+            table(
+                "users",
+                vec![
+                    primary_key("id", "int4"),
+                    column("name", "text", false),
+                    Column {
+                        is_unique: true,
+                        ..column("email", "citext", false)
+                    },
+                ],
+            ),
+            table(
+                "posts",
+                vec![
+                    primary_key("id", "int8"),
+                    foreign_key("author_id", "int4", "users", "id"),
+                    // references a unique column that is not the primary key
+                    foreign_key("author_email", "citext", "users", "email"),
+                    Column {
+                        is_nullable: true,
+                        ..foreign_key("reply_to", "int8", "posts", "id")
+                    },
+                    // references a table with a typed id in a schema that is not generated
+                    Column {
+                        foreign_key_schema: Some("auth".to_string()),
+                        ..foreign_key("reviewer_id", "int4", "users", "id")
+                    },
+                ],
+            ),
+        ],
+    }
+}
+
+pub fn test_typed_ids() -> Result<(), Error> {
+    let schema = typed_ids_schema();
+    let options = || Options {
+        framework: Framework::Sqlx,
+        derives: ["Debug", "PartialEq", "Eq", "Hash"]
+            .iter()
+            .map(|d| d.to_string())
+            .collect(),
+        derive_copy_when_possible: true,
+        typed_ids: true,
+        ..Default::default()
+    };
+
+    let generator = Generator::new(options(), Box::new(PostgresTypes));
+    assert_snapshot(
+        "typed_ids_module",
+        &module_source(&generator.generate_code_from(&schema), DEFAULT_FILE_SUFFIX),
+    )?;
+    assert_golden("typed_ids", options(), &schema)
+}
+
+pub fn test_phantom_ids() -> Result<(), Error> {
+    let schema = typed_ids_schema();
+    let options = || {
+        Options::default()
+            .framework(Framework::Sqlx)
+            .derives(["Debug"])
+            .typed_ids(true)
+            .phantom_ids(true)
+    };
+
+    let generator = Generator::new(options(), Box::new(PostgresTypes));
+    assert_snapshot(
+        "phantom_ids_module",
+        &module_source(&generator.generate_code_from(&schema), DEFAULT_FILE_SUFFIX),
+    )?;
+    assert_golden("phantom_ids", options(), &schema)
+}
+
+pub fn test_citext_newtype() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.retain(|table| table.name == "users");
+    let options = Options {
+        framework: Framework::Sqlx,
+        derives: vec![
+            "Debug".to_string(),
+            "Clone".to_string(),
+            "PartialEq".to_string(),
+        ],
+        citext_newtype: true,
+        ..Default::default()
+    };
+    assert_golden("citext_newtype", options, &schema)
+}
+
+pub fn test_interval_struct() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "tasks",
+            vec![
+                primary_key("id", "int4"),
+                column("estimate", "interval", false),
+                column("spent", "interval", true),
+                column("reminders", "_interval", false),
+            ],
+        )],
+    };
+    let options = Options::default()
+        .framework(Framework::Sqlx)
+        .derives(["Debug", "Clone", "Copy", "PartialEq"])
+        .interval_struct(true);
+    assert_golden("interval_struct", options, &schema)
+}
+
+pub fn test_serde_null_as_missing() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.retain(|table| table.name == "users");
+    let options = Options::default()
+        .derives(["Debug", "serde::Serialize", "serde::Deserialize"])
+        .serde_null_as_missing(true);
+    assert_golden("serde_null_as_missing", options, &schema)
+}
+
+pub fn test_display_field() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema
+        .tables
+        .retain(|table| ["users", "measurements"].contains(&table.name.as_str()));
+    let options = Options::default()
+        .derives(["Debug"])
+        .display_field("users", "email")
+        .display_field("measurements", "value");
+    assert_golden("display_field", options, &schema)
+}
+
+pub fn test_system_types() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "locks",
+            vec![
+                primary_key("id", "int4"),
+                column("relation_oid", "oid", false),
+                column("relation", "regclass", true),
+                column("transaction_id", "xid", false),
+            ],
+        )],
+    };
+    let options = Options::default()
+        .framework(Framework::Sqlx)
+        .derives(["Debug", "PartialEq", "PartialOrd"])
+        .crud(true);
+    assert_golden("system_types", options, &schema)
+}
+
+pub fn test_sqlx_type_derives() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![
+            table(
+                "payments",
+                vec![primary_key("id", "int4"), column("amount", "money", false)],
+            ),
+            table(
+                "bookings",
+                vec![
+                    primary_key("id", "int4"),
+                    column("during", "tstzrange", false),
+                    column("seats", "int4range", true),
+                ],
+            ),
+            table(
+                "openings",
+                vec![
+                    primary_key("id", "int4"),
+                    column("opens_at", "timetz", false),
+                ],
+            ),
+        ],
+    };
+    let options = Options::default().framework(Framework::Sqlx).derives([
+        "Debug",
+        "PartialEq",
+        "Hash",
+        "Default",
+    ]);
+    assert_golden("sqlx_type_derives", options, &schema)
+}
+
+pub fn test_module_source_ignores_order() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.reverse();
+    let generator = Generator::new(Options::default(), Box::new(PostgresTypes));
+    assert_snapshot(
+        "module_source",
+        &module_source(&generator.generate_code_from(&schema), DEFAULT_FILE_SUFFIX),
+    )
+}
+
+pub fn test_field_name_collision() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "sessions",
+            vec![
+                primary_key("id", "int4"),
+                column("user_id", "int4", false),
+                column("UserId", "int4", true),
+            ],
+        )],
+    };
+    let options = Options {
+        framework: Framework::Sqlx,
+        ..Default::default()
+    };
+    assert_golden("field_name_collision", options, &schema)
+}
+
+/// A schema with columns that sqlx provides dedicated types for
+fn sqlx_types_schema() -> DatabaseSchema {
+    DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "subscriptions",
+            vec![
+                primary_key("id", "int4"),
+                column("price", "money", false),
+                column("billing_period", "interval", false),
+                column("active_during", "tstzrange", true),
+                column("seats", "_int4range", false),
+            ],
+        )],
+    }
+}
+
+pub fn test_plain_types_without_framework() -> Result<(), Error> {
+    assert_golden(
+        "plain_types_without_framework",
+        Options::default(),
+        &sqlx_types_schema(),
+    )?;
+
+    // an explicitly chosen interval format is kept without a framework
+    let options = Options::default().interval_format(IntervalFormat::Pg);
+    let generator = Generator::new(options, Box::new(PostgresTypes));
+    let code: String = generator
+        .generate_code_from(&sqlx_types_schema())
+        .iter()
+        .map(|snippet| snippet.code.as_str())
+        .collect();
+    if !code.contains("pub billing_period: PgInterval,") {
+        bail!("expected an explicit --interval-as pg to keep PgInterval without a framework but got\n{code}");
+    }
+
+    let options = Options {
+        framework: Framework::Sqlx,
+        ..Default::default()
+    };
+    assert_golden("sqlx_types", options, &sqlx_types_schema())
+}
+
+pub fn test_serde_skips_unsupported_types() -> Result<(), Error> {
+    let mut schema = sqlx_types_schema();
+    // a range has no default value, so it can't be skipped when deserializing
+    schema.tables.push(table(
+        "reservations",
+        vec![
+            primary_key("id", "int4"),
+            column("period", "tstzrange", false),
+        ],
+    ));
+    let options = Options {
+        framework: Framework::Sqlx,
+        derives: vec!["Serialize".to_string(), "serde::Deserialize".to_string()],
+        ..Default::default()
+    };
+    assert_golden("serde_skips_unsupported_types", options, &schema)
+}
+
+pub fn test_file_suffix() -> Result<(), Error> {
+    let generator = Generator::new(Options::default(), Box::new(PostgresTypes));
+    let snippets = generator.generate_code_from(&basic_schema());
+    assert_snapshot("file_suffix_module", &module_source(&snippets, ".gen.rs"))
+}
+
+pub fn test_annotate_types() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables[0].columns[1].character_maximum_length = Some(255);
+    schema.tables[1].columns[2].numeric_precision = Some(10);
+    schema.tables[1].columns[2].numeric_scale = Some(2);
+    let options = Options {
+        annotate_types: true,
+        ..Default::default()
+    };
+    assert_golden("annotate_types", options, &schema)
+}
+
+pub fn test_case_sensitive_identifiers() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![Enum {
+            schema: "Billing".to_string(),
+            name: "TicketState".to_string(),
+            values: ["Open", "on hold"]
+                .iter()
+                .enumerate()
+                .map(|(i, value)| EnumValue {
+                    name: value.to_string(),
+                    order: i as f32,
+                    discriminant: None,
+                })
+                .collect(),
+            backing_type: None,
+        }],
+        composite_types: vec![],
+        tables: vec![table(
+            "UserData",
+            vec![
+                primary_key("Id", "int4"),
+                column("displayName", "text", false),
+                Column {
+                    udt_schema: "Billing".to_string(),
+                    ..column("state", "TicketState", false)
+                },
+            ],
+        )],
+    };
+    let options = Options {
+        framework: Framework::Sqlx,
+        ..Default::default()
+    };
+    assert_golden("case_sensitive_identifiers", options, &schema)
+}
+
+pub fn test_prelude_source() -> Result<(), Error> {
+    let generator = Generator::new(Options::default(), Box::new(PostgresTypes));
+    assert_snapshot(
+        "prelude_source",
+        &prelude_source(&generator.generate_code_from(&basic_schema())),
+    )
+}
+
+pub fn test_arrays_of_user_types() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables = vec![table(
+        "deliveries",
+        vec![
+            primary_key("id", "int4"),
+            column("stops", "_address", false),
+            column("mood_history", "__mood", true),
+            column("windows", "_tstzrange", false),
+        ],
+    )];
+    assert_golden("arrays_of_user_types", Options::default(), &schema)
+}
+
+pub fn test_types_only() -> Result<(), Error> {
+    let options = || Options {
+        types_only: true,
+        citext_newtype: true,
+        ..Default::default()
+    };
+    assert_golden("types_only", options(), &basic_schema())?;
+    let generator = Generator::new(options(), Box::new(PostgresTypes));
+    let snippets = generator.generate_code_from(&basic_schema());
+    assert_snapshot(
+        "types_only_module",
+        &module_source(&snippets, DEFAULT_FILE_SUFFIX),
+    )
+}
+
+pub async fn test_write_changed() -> Result<(), Error> {
+    let target_dir = env::temp_dir().join("autostruct_write_changed");
+    if target_dir.exists() {
+        fs::remove_dir_all(&target_dir)?;
+    }
+    let generator = Generator::new(Options::default(), Box::new(PostgresTypes));
+    let mut schema = basic_schema();
+
+    let written = write_changed(&generator.generate_code_from(&schema), &target_dir).await?;
+    if written.len() != 5 {
+        bail!("expected the first run to write every file but it wrote {written:?}");
+    }
+
+    // editing a single table only rewrites its file
+    schema.tables[1].columns.push(column("unit", "text", true));
+    let written = write_changed(&generator.generate_code_from(&schema), &target_dir).await?;
+    if written != ["measurements.rs"] {
+        bail!("expected only measurements.rs to be rewritten but got {written:?}");
+    }
+
+    // dropping a table removes its file and rewrites the mod.rs that declared it
+    schema.tables.remove(1);
+    let written = write_changed(&generator.generate_code_from(&schema), &target_dir).await?;
+    if written != ["mod.rs"] || target_dir.join("measurements.rs").exists() {
+        bail!("expected measurements.rs to be removed and only mod.rs to be rewritten but got {written:?}");
+    }
+
+    fs::remove_dir_all(&target_dir)?;
+    Ok(())
+}
+
+pub fn test_options_setters() -> Result<(), Error> {
+    // the setters configure the same generation as the fields they set
+    let options = Options::default()
+        .derives(["Debug", "Clone", "PartialEq", "Eq"])
+        .derive_copy_when_possible(true)
+        .constructors(true);
+    assert_golden("derives_and_constructors", options, &basic_schema())
+}
+
+pub fn test_query_macros() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables.push(table(
+        "order_items",
+        vec![
+            primary_key("order_id", "int8"),
+            primary_key("position", "int4"),
+            column("price", "money", false),
+        ],
+    ));
+    let options = Options::default()
+        .framework(Framework::Sqlx)
+        .query_macros(true)
+        .typed_ids(true)
+        .money_as_decimal(true);
+    assert_golden("query_macros", options, &schema)
+}
+
+pub fn test_foreign_table() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![Table {
+            is_foreign: true,
+            ..table(
+                "remote_events",
+                vec![
+                    column("id", "int8", false),
+                    column("payload", "jsonb", true),
+                ],
+            )
+        }],
+    };
+    let options = Options::default()
+        .framework(Framework::Sqlx)
+        .typed_ids(true)
+        .constructors(true);
+    assert_golden("foreign_table", options, &schema)
+}
+
+pub fn test_nullable_array_elements() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables = vec![table(
+        "readings",
+        vec![
+            primary_key("id", "int4"),
+            column("samples", "_int4", false),
+            column("corrections", "_int4", true),
+        ],
+    )];
+    schema.composite_types[0].attributes.push(Attribute {
+        name: "lines".to_string(),
+        data_type: "_text".to_string(),
+        data_type_schema: "pg_catalog".to_string(),
+    });
+    let options = Options::default().nullable_array_elements(true);
+    assert_golden("nullable_array_elements", options, &schema)
+}
+
+pub fn test_column_list() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    let mut user = table(
+        "users",
+        vec![
+            primary_key("id", "int4"),
+            column("displayName", "text", false),
+            column("internal", "text", true),
+        ],
+    );
+    user.columns[2].comment = Some("@autostruct:skip".to_string());
+    schema.tables = vec![user];
+    let options = Options::default().column_list(true);
+    assert_golden("column_list", options, &schema)
+}
+
+pub fn test_materialized_view() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![Table {
+            is_materialized_view: true,
+            ..table(
+                "monthly_sales",
+                vec![
+                    column("month", "date", true),
+                    column("total", "numeric", true),
+                ],
+            )
+        }],
+    };
+    let options = Options::default()
+        .framework(Framework::Sqlx)
+        .constructors(true);
+    assert_golden("materialized_view", options, &schema)
+}
+
+pub fn test_struct_attributes() -> Result<(), Error> {
+    let options = Options::default()
+        .derives(["Debug"])
+        .struct_attributes(["#[cfg_attr(test, derive(Default))]", "#[allow(dead_code)]"])
+        .typed_ids(true)
+        .citext_newtype(true);
+    assert_golden("struct_attributes", options, &basic_schema())
+}
+
+pub fn test_annotate_defaults() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "sessions",
+            vec![
+                Column {
+                    column_default: Some("gen_random_uuid()".to_string()),
+                    ..primary_key("id", "uuid")
+                },
+                column("user_id", "int4", false),
+                Column {
+                    column_default: Some("now()".to_string()),
+                    ..column("created_at", "timestamptz", false)
+                },
+                Column {
+                    column_default: Some("(now() +\n    '1 day'::interval)".to_string()),
+                    ..column("expires_at", "timestamptz", false)
+                },
+            ],
+        )],
+    };
+    let options = Options::default().annotate_defaults(true);
+    assert_golden("annotate_defaults", options, &schema)
+}
+
+pub fn test_rename_all() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![
+            table(
+                "accounts",
+                vec![
+                    primary_key("id", "int4"),
+                    column("displayName", "text", false),
+                    column("createdAt", "timestamptz", false),
+                ],
+            ),
+            table(
+                "audit",
+                vec![
+                    primary_key("ID", "int4"),
+                    column("EVENT_NAME", "text", false),
+                ],
+            ),
+            table(
+                "imports",
+                vec![
+                    primary_key("id", "int4"),
+                    column("fileName", "text", false),
+                    column("row_count", "int4", false),
+                ],
+            ),
+        ],
+    };
+    let options = Options {
+        framework: Framework::Sqlx,
+        ..Default::default()
+    };
+    assert_golden("rename_all", options, &schema)
+}
+
+pub fn test_crud() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![
+            table(
+                "orders",
+                vec![
+                    Column {
+                        is_generated: true,
+                        ..primary_key("id", "int8")
+                    },
+                    column("customer", "text", false),
+                    column("price", "money", false),
+                    column("note", "text", true),
+                    Column {
+                        column_default: Some("now()".to_string()),
+                        ..column("created_at", "timestamptz", false)
+                    },
+                    Column {
+                        is_generated: true,
+                        ..column("total", "int8", true)
+                    },
+                ],
+            ),
+            table(
+                "events",
+                vec![
+                    column("name", "text", false),
+                    column("payload", "jsonb", true),
+                ],
+            ),
+        ],
+    };
+    let options = Options {
+        framework: Framework::Sqlx,
+        derives: vec!["Debug".to_string()],
+        money_as_decimal: true,
+        crud: true,
+        ..Default::default()
+    };
+    assert_golden("crud", options, &schema)
+}
+
+pub fn test_list_mappings() -> Result<(), Error> {
+    let mut schema = basic_schema();
+    schema.tables[0]
+        .columns
+        .push(column("location", "geography", true));
+    let generator = Generator::new(
+        Options::default().nullable_array_elements(true),
+        Box::new(PostgresTypes),
+    );
+    let mappings = generator
+        .list_mappings_from(&schema)
+        .iter()
+        .map(|mapping| format!("{mapping}\n"))
+        .collect::<String>();
+    assert_snapshot("list_mappings", &mappings)
+}
+
+pub fn test_time_with_time_zone() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "opening_hours",
+            vec![
+                primary_key("id", "int4"),
+                column("opens_at", "time", false),
+                column("closes_at", "timetz", true),
+            ],
+        )],
+    };
+    let options = || {
+        Options::default()
+            .derives(["Debug", "PartialEq", "Eq", "Hash", "Serialize"])
+            .derive_copy_when_possible(true)
+    };
+    assert_golden(
+        "time_with_time_zone",
+        options().framework(Framework::Sqlx),
+        &schema,
+    )?;
+    assert_golden("time_with_time_zone_plain", options(), &schema)
+}
+
+pub fn test_ordered_derives() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![
+            table(
+                "events",
+                vec![
+                    primary_key("id", "int8"),
+                    column("name", "text", false),
+                    column("starts_at", "timestamptz", false),
+                ],
+            ),
+            table(
+                "measurements",
+                vec![primary_key("id", "int8"), column("value", "float8", false)],
+            ),
+            table(
+                "documents",
+                vec![primary_key("id", "int8"), column("body", "jsonb", false)],
+            ),
+            table(
+                "bookings",
+                vec![
+                    primary_key("id", "int8"),
+                    column("during", "tstzrange", false),
+                    column("duration", "interval", true),
+                ],
+            ),
+        ],
+    };
+    let options = Options::default().framework(Framework::Sqlx).derives([
+        "Debug",
+        "PartialEq",
+        "PartialOrd",
+        "Eq",
+        "Ord",
+    ]);
+    assert_golden("ordered_derives", options, &schema)
+}
+
+pub fn test_fixed_binary() -> Result<(), Error> {
+    let bits = |name: &str, length: i32| Column {
+        character_maximum_length: Some(length),
+        ..column(name, "bit", false)
+    };
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "files",
+            vec![
+                primary_key("id", "int4"),
+                bits("checksum", 256),
+                bits("flags", 12),
+                column("content", "bytea", false),
+            ],
+        )],
+    };
+    let options = Options::default()
+        .framework(Framework::Sqlx)
+        .derives(["Debug", "Default", "Serialize"])
+        .fixed_binary(true)
+        .query_macros(true);
+    assert_golden("fixed_binary", options, &schema)
+}
+
+pub fn test_enum_try_from() -> Result<(), Error> {
+    let options = || Options::default().enum_try_from(true);
+    assert_golden("enum_try_from", options(), &basic_schema())?;
+    assert_golden(
+        "enum_try_from_unknown_variant",
+        options().enum_unknown_variant(true),
+        &basic_schema(),
+    )
+}
+
+pub fn test_parse_ignore_file() -> Result<(), Error> {
+    let content = "# bookkeeping tables\naudit_*\n\n  schema_migrations  \n*.search_vector\nusers.password_hash\n";
+    let (tables, columns) = parse_ignore_file(content);
+    if tables != ["audit_*", "schema_migrations"] {
+        bail!("expected the ignore file to exclude the tables audit_* and schema_migrations but got {tables:?}");
+    }
+    if columns != ["*.search_vector", "users.password_hash"] {
+        bail!("expected the ignore file to exclude the columns *.search_vector and users.password_hash but got {columns:?}");
+    }
+    Ok(())
+}
+
+pub fn test_schema_hash() -> Result<(), Error> {
+    let schema = basic_schema();
+    let mut reordered = basic_schema();
+    reordered.tables.reverse();
+    for table in &mut reordered.tables {
+        table.comment = Some("comments are not part of the hash".to_string());
+    }
+    if schema.hash() != reordered.hash() {
+        bail!("expected reordering the tables and changing their comments to keep the hash of the schema");
+    }
+
+    let mut changed = basic_schema();
+    changed.tables[0].columns[1].is_nullable = !changed.tables[0].columns[1].is_nullable;
+    if schema.hash() == changed.hash() {
+        bail!("expected changing the nullability of a column to change the hash of the schema");
+    }
+    Ok(())
+}
+
+pub fn test_singular_struct_names() -> Result<(), Error> {
+    let options = Options::default().singular(true);
+    let generator = Generator::new(options, Box::new(PostgresTypes));
+    let snippets = generator.generate_code_from(&basic_schema());
+    let module = module_source(&snippets, DEFAULT_FILE_SUFFIX);
+    if !module.contains("pub mod users;\npub use users::User;\n") {
+        bail!("expected the singular struct of the users table to be defined in its plural module but got\n{module}");
+    }
+
+    let options = Options::default().singular(true).singular_modules(true);
+    let generator = Generator::new(options, Box::new(PostgresTypes));
+    let snippets = generator.generate_code_from(&basic_schema());
+    let module = module_source(&snippets, DEFAULT_FILE_SUFFIX);
+    if !module.contains("pub mod user;\npub use user::User;\n") {
+        bail!("expected the singular struct of the users table to be defined in a singular module but got\n{module}");
+    }
+    Ok(())
+}
+
+pub fn test_invalid_identifiers() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![],
+        tables: vec![table(
+            "people",
+            vec![
+                primary_key("id", "int4"),
+                column("first name", "text", false),
+                column("first_name", "text", false),
+                column("2fa", "bool", false),
+                column("type", "text", false),
+                column("τιμή", "int4", false),
+            ],
+        )],
+    };
+    let options = Options::default().framework(Framework::Sqlx);
+    let generator = Generator::new(options, Box::new(PostgresTypes));
+    let code: String = generator
+        .generate_code_from(&schema)
+        .iter()
+        .map(|snippet| snippet.code.as_str())
+        .collect();
+    for field in [
+        "    #[sqlx(rename = \"first name\")]\n    pub first_name: String,\n",
+        "    #[sqlx(rename = \"first_name\")]\n    pub first_name_2: String,\n",
+        "    #[sqlx(rename = \"2fa\")]\n    pub _2fa: bool,\n",
+        "    #[sqlx(rename = \"type\")]\n    pub type_: String,\n",
+        "    pub τιμή: i32,\n",
+    ] {
+        if !code.contains(field) {
+            bail!("expected the field\n{field}to be generated but got\n{code}");
+        }
+    }
+    Ok(())
+}
+
+pub fn test_enum_value_order() -> Result<(), Error> {
+    let value = |name: &str, order: f32| EnumValue {
+        name: name.to_string(),
+        order,
+        discriminant: None,
+    };
+    let mut values = [value("sad", 2.0), value("ok", 1.0), value("happy", 1.0)];
+    values.sort_by(EnumValue::cmp_order);
+    let names: Vec<&str> = values.iter().map(|value| value.name.as_str()).collect();
+    if names != ["happy", "ok", "sad"] {
+        bail!("expected values with the same order to be sorted by name but got {names:?}");
+    }
+
+    let mut schema = basic_schema();
+    schema.enumerations[0].values = vec![value("ok", 1.0), value("happy", 1.0)];
+    let mut reordered = basic_schema();
+    reordered.enumerations[0].values = vec![value("happy", 1.0), value("ok", 1.0)];
+    if schema.hash() != reordered.hash() {
+        bail!("expected the order values with the same order were retrieved in to keep the hash of the schema");
+    }
+    Ok(())
+}
+
+pub fn test_types_of_other_schemas() -> Result<(), Error> {
+    let role = |schema: &str, values: &[&str]| Enum {
+        schema: schema.to_string(),
+        name: "role".to_string(),
+        values: values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| EnumValue {
+                name: value.to_string(),
+                order: i as f32,
+                discriminant: None,
+            })
+            .collect(),
+        backing_type: None,
+    };
+    let auth_role = |name: &str, udt_name: &str| Column {
+        udt_schema: "auth".to_string(),
+        ..column(name, udt_name, false)
+    };
+    let schema = DatabaseSchema {
+        enumerations: vec![
+            role("auth", &["owner", "guest"]),
+            role("public", &["admin", "member"]),
+        ],
+        composite_types: vec![CompositeType {
+            schema: "public".to_string(),
+            name: "grant".to_string(),
+            attributes: vec![Attribute {
+                name: "role".to_string(),
+                data_type: "role".to_string(),
+                data_type_schema: "auth".to_string(),
+            }],
+        }],
+        tables: vec![table(
+            "members",
+            vec![
+                primary_key("id", "int4"),
+                column("role", "role", false),
+                auth_role("login_role", "role"),
+                auth_role("previous_login_roles", "_role"),
+            ],
+        )],
+    };
+    let options = Options::default().framework(Framework::Sqlx);
+    assert_golden("types_of_other_schemas", options, &schema)
+}
+
+pub fn test_composite_field_derives() -> Result<(), Error> {
+    let schema = DatabaseSchema {
+        enumerations: vec![],
+        composite_types: vec![CompositeType {
+            schema: "public".to_string(),
+            name: "position".to_string(),
+            attributes: vec![
+                Attribute {
+                    name: "latitude".to_string(),
+                    data_type: "float8".to_string(),
+                    data_type_schema: "pg_catalog".to_string(),
+                },
+                Attribute {
+                    name: "longitude".to_string(),
+                    data_type: "float8".to_string(),
+                    data_type_schema: "pg_catalog".to_string(),
+                },
+            ],
+        }],
+        tables: vec![table(
+            "stops",
+            vec![
+                primary_key("id", "int4"),
+                column("position", "position", false),
+                column("previous_positions", "_position", false),
+            ],
+        )],
+    };
+    let options =
+        Options::default().derives(["Debug", "PartialEq", "Eq", "Hash", "PartialOrd", "Ord"]);
+    assert_golden("composite_field_derives", options, &schema)
+}
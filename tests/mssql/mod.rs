@@ -0,0 +1,28 @@
+/*!
+Tests for the SQL Server type mapping, which do not require a database
+*/
+
+use anyhow::{bail, Error};
+use autostruct::database::mssql;
+
+pub fn test_type_mapping() -> Result<(), Error> {
+    let cases = [
+        ("uniqueidentifier", "Uuid"),
+        ("datetimeoffset", "DateTime<FixedOffset>"),
+        ("datetime2", "NaiveDateTime"),
+        ("rowversion", "Vec<u8>"),
+        ("timestamp", "Vec<u8>"),
+        ("tinyint", "i16"),
+        ("nvarchar", "String"),
+        ("bit", "bool"),
+    ];
+
+    for (data_type, expected) in cases {
+        let actual = mssql::type_name_from(data_type).to_string();
+        if actual != expected {
+            bail!("expected `{data_type}` to map to `{expected}` but got `{actual}`");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+/*!
+Tests for the MySQL type mapping, which do not require a database
+*/
+
+use anyhow::{bail, Error};
+use autostruct::database::mysql;
+
+pub fn test_unsigned_integers() -> Result<(), Error> {
+    let cases = [
+        ("tinyint unsigned", "u8"),
+        ("tinyint(1) unsigned", "u8"),
+        ("smallint unsigned", "u16"),
+        ("mediumint unsigned", "u32"),
+        ("int unsigned", "u32"),
+        ("int(10) unsigned zerofill", "u32"),
+        ("bigint unsigned", "u64"),
+        ("tinyint", "i8"),
+        ("smallint", "i16"),
+        ("mediumint", "i32"),
+        ("int", "i32"),
+        ("bigint", "i64"),
+        ("tinyint(1)", "bool"),
+    ];
+
+    for (column_type, expected) in cases {
+        let actual = mysql::type_name_from(column_type).to_string();
+        if actual != expected {
+            bail!("expected `{column_type}` to map to `{expected}` but got `{actual}`");
+        }
+    }
+
+    Ok(())
+}
@@ -145,16 +145,28 @@ CREATE TABLE table_enum_type (
     mood_column mood NOT NULL
 );
 
+CREATE SCHEMA auth;
+CREATE TYPE auth.role AS ENUM ('reader', 'editor', 'admin');
+
+CREATE TABLE table_other_schema_enum_type (
+    id SERIAL PRIMARY KEY,
+    role_column auth.role NOT NULL
+);
+
 -- Table 17: Foreign Data Wrapper Types
 CREATE TABLE table_fdw (
     id SERIAL PRIMARY KEY,
     foreign_data_column VARCHAR(255)
 );
 
+COMMENT ON COLUMN table_fdw.foreign_data_column IS '@autostruct:rename=foreign_data';
+
 -- Table 18: Object Identifier Types
 CREATE TABLE table_oid_types (
     id SERIAL PRIMARY KEY,
-    oid_column OID NOT NULL
+    oid_column OID NOT NULL,
+    regclass_column REGCLASS,
+    xid_column XID
 );
 
 -- Table 19: Other Special Types
@@ -165,7 +177,20 @@ CREATE TABLE table_special_types (
     uuid_column UUID
 );
 
--- Table 20: Foreign Keys
+-- Table 20: Partitioned Table
+CREATE TABLE table_partitioned (
+    id SERIAL,
+    created_at DATE NOT NULL,
+    PRIMARY KEY (id, created_at)
+) PARTITION BY RANGE (created_at);
+
+CREATE TABLE table_partitioned_2024 PARTITION OF table_partitioned
+    FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');
+
+CREATE TABLE table_partitioned_2025 PARTITION OF table_partitioned
+    FOR VALUES FROM ('2025-01-01') TO ('2026-01-01');
+
+-- Table 21: Foreign Keys
 CREATE TABLE table_foreign_keys (
     id SERIAL PRIMARY KEY,
     fk_basic INT REFERENCES table_basic_types(id),
@@ -1,4 +1,13 @@
+#[cfg(feature = "postgres")]
+mod golden;
+#[cfg(feature = "mssql")]
+mod mssql;
+#[cfg(feature = "mysql")]
+mod mysql;
+#[cfg(feature = "postgres_test")]
 mod postgres;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
 #[cfg(feature = "postgres_test")]
 #[tokio::test]
@@ -7,3 +16,481 @@ async fn test_postgres() {
         .await
         .expect("postgres integration test failed");
 }
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_typed_ids() {
+    postgres::test_typed_ids()
+        .await
+        .expect("postgres typed ids test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_profiles() {
+    postgres::test_profiles()
+        .await
+        .expect("postgres profiles test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_types_of_other_schemas() {
+    postgres::test_types_of_other_schemas()
+        .await
+        .expect("postgres types of other schemas test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_glob_exclusions() {
+    postgres::test_glob_exclusions()
+        .await
+        .expect("postgres glob exclusions test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_multiple_databases() {
+    postgres::test_multiple_databases()
+        .await
+        .expect("postgres multiple databases test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_pg_catalog() {
+    postgres::test_pg_catalog()
+        .await
+        .expect("postgres pg_catalog test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_table_order() {
+    postgres::test_table_order()
+        .await
+        .expect("postgres table order test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_no_banner() {
+    postgres::test_no_banner()
+        .await
+        .expect("postgres no banner test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_module_doc() {
+    postgres::test_module_doc()
+        .await
+        .expect("postgres module doc test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_search_path() {
+    postgres::test_search_path()
+        .await
+        .expect("postgres search path test failed");
+}
+
+#[cfg(feature = "postgres_test")]
+#[tokio::test]
+async fn test_postgres_excluded_tables() {
+    postgres::test_excluded_tables()
+        .await
+        .expect("postgres excluded tables test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_default_options() {
+    golden::test_default_options().expect("golden default options test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_derives_and_constructors() {
+    golden::test_derives_and_constructors().expect("golden derives and constructors test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_module_source() {
+    golden::test_module_source().expect("golden module source test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_empty_schema() {
+    golden::test_empty_schema().expect("golden empty schema test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_list_entries() {
+    golden::test_list_entries().expect("golden list entries test failed");
+}
+
+#[cfg(feature = "mysql")]
+#[test]
+fn test_mysql_unsigned_integers() {
+    mysql::test_unsigned_integers().expect("mysql unsigned integers test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_sqlx_framework() {
+    golden::test_sqlx_framework().expect("golden sqlx framework test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_manual_from_row() {
+    golden::test_manual_from_row().expect("golden manual from row test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_column_overrides() {
+    golden::test_column_overrides().expect("golden column overrides test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_json_column_override() {
+    golden::test_json_column_override().expect("golden json column override test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_bitflags() {
+    golden::test_bitflags().expect("golden bitflags test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_rename_all() {
+    golden::test_rename_all().expect("golden rename all test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_crud() {
+    golden::test_crud().expect("golden crud test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_system_types() {
+    golden::test_system_types().expect("golden system types test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_sqlx_type_derives() {
+    golden::test_sqlx_type_derives().expect("golden sqlx type derives test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_rename_type_on_collision() {
+    golden::test_rename_type_on_collision().expect("golden rename type on collision test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_rename_table_on_collision() {
+    golden::test_rename_table_on_collision().expect("golden rename table on collision test failed");
+}
+
+#[cfg(feature = "mssql")]
+#[test]
+fn test_mssql_type_mapping() {
+    mssql::test_type_mapping().expect("mssql type mapping test failed");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_type_affinity() {
+    sqlite::test_type_affinity().expect("sqlite type affinity test failed");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_blocking_provider() {
+    sqlite::test_blocking_provider().expect("sqlite blocking provider test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_tab_indent() {
+    golden::test_tab_indent().expect("golden tab indent test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_crlf_line_endings() {
+    golden::test_crlf_line_endings().expect("golden crlf line endings test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_enum_from_table() {
+    golden::test_enum_from_table().expect("golden enum from table test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_write_all() {
+    golden::test_write_all().expect("golden write all test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_non_exhaustive_enums() {
+    golden::test_non_exhaustive_enums().expect("golden non exhaustive enums test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_non_exhaustive_sqlx_enums() {
+    golden::test_non_exhaustive_sqlx_enums().expect("golden non exhaustive sqlx enums test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_enum_unknown_variant() {
+    golden::test_enum_unknown_variant().expect("golden enum unknown variant test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_typed_ids() {
+    golden::test_typed_ids().expect("golden typed ids test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_phantom_ids() {
+    golden::test_phantom_ids().expect("golden phantom ids test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_citext_newtype() {
+    golden::test_citext_newtype().expect("golden citext newtype test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_interval_struct() {
+    golden::test_interval_struct().expect("golden interval struct test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_serde_null_as_missing() {
+    golden::test_serde_null_as_missing().expect("golden serde null as missing test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_display_field() {
+    golden::test_display_field().expect("golden display field test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_module_source_ignores_order() {
+    golden::test_module_source_ignores_order()
+        .expect("golden module source ignores order test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_field_name_collision() {
+    golden::test_field_name_collision().expect("golden field name collision test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_plain_types_without_framework() {
+    golden::test_plain_types_without_framework()
+        .expect("golden plain types without framework test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_serde_skips_unsupported_types() {
+    golden::test_serde_skips_unsupported_types()
+        .expect("golden serde skips unsupported types test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_file_suffix() {
+    golden::test_file_suffix().expect("golden file suffix test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_annotate_types() {
+    golden::test_annotate_types().expect("golden annotate types test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_case_sensitive_identifiers() {
+    golden::test_case_sensitive_identifiers()
+        .expect("golden case sensitive identifiers test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_prelude_source() {
+    golden::test_prelude_source().expect("golden prelude source test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_arrays_of_user_types() {
+    golden::test_arrays_of_user_types().expect("golden arrays of user types test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_types_only() {
+    golden::test_types_only().expect("golden types only test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn test_golden_write_changed() {
+    golden::test_write_changed()
+        .await
+        .expect("golden write changed test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_options_setters() {
+    golden::test_options_setters().expect("golden options setters test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_query_macros() {
+    golden::test_query_macros().expect("golden query macros test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_foreign_table() {
+    golden::test_foreign_table().expect("golden foreign table test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_nullable_array_elements() {
+    golden::test_nullable_array_elements().expect("golden nullable array elements test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_column_list() {
+    golden::test_column_list().expect("golden column list test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_materialized_view() {
+    golden::test_materialized_view().expect("golden materialized view test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_struct_attributes() {
+    golden::test_struct_attributes().expect("golden struct attributes test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_annotate_defaults() {
+    golden::test_annotate_defaults().expect("golden annotate defaults test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_list_mappings() {
+    golden::test_list_mappings().expect("golden list mappings test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_time_with_time_zone() {
+    golden::test_time_with_time_zone().expect("golden time with time zone test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_ordered_derives() {
+    golden::test_ordered_derives().expect("golden ordered derives test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_fixed_binary() {
+    golden::test_fixed_binary().expect("golden fixed binary test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_enum_try_from() {
+    golden::test_enum_try_from().expect("golden enum try from test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_parse_ignore_file() {
+    golden::test_parse_ignore_file().expect("golden parse ignore file test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_singular_struct_names() {
+    golden::test_singular_struct_names().expect("golden singular struct names test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_invalid_identifiers() {
+    golden::test_invalid_identifiers().expect("golden invalid identifiers test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_enum_value_order() {
+    golden::test_enum_value_order().expect("golden enum value order test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_schema_hash() {
+    golden::test_schema_hash().expect("golden schema hash test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_types_of_other_schemas() {
+    golden::test_types_of_other_schemas().expect("golden types of other schemas test failed");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_golden_composite_field_derives() {
+    golden::test_composite_field_derives().expect("golden composite field derives test failed");
+}
@@ -0,0 +1,107 @@
+/*!
+Tests for the SQLite type mapping and for generating code with a synchronous provider, which do not require a database
+*/
+
+use std::{env, fs};
+
+use anyhow::{bail, Context, Error};
+use autostruct::{
+    database::{sqlite, Column, DatabaseSchema, SyncInfoProvider, Table},
+    generator, rust,
+};
+
+/// A synchronous provider like one backed by `rusqlite` would be, which returns a constructed schema
+struct SqliteTypes;
+
+impl SyncInfoProvider for SqliteTypes {
+    fn type_name_from(&self, db_type: &str) -> rust::Type {
+        sqlite::type_name_from(db_type, false)
+    }
+
+    fn get_schema(&self) -> Result<DatabaseSchema, Error> {
+        let column = |name: &str, udt_name: &str| Column {
+            name: name.to_string(),
+            udt_name: udt_name.to_string(),
+            udt_schema: "main".to_string(),
+            data_type: udt_name.to_string(),
+            numeric_precision: None,
+            numeric_scale: None,
+            character_maximum_length: None,
+            is_nullable: false,
+            column_default: None,
+            is_generated: false,
+            is_unique: false,
+            is_primary_key: false,
+            foreign_key_schema: None,
+            foreign_key_table: None,
+            foreign_key_id: None,
+            table_schema: "main".to_string(),
+            is_writable: true,
+            comment: None,
+        };
+        Ok(DatabaseSchema {
+            enumerations: vec![],
+            composite_types: vec![],
+            tables: vec![Table {
+                name: "notes".to_string(),
+                comment: None,
+                columns: vec![column("id", "INTEGER"), column("body", "TEXT")],
+                is_foreign: false,
+                is_materialized_view: false,
+            }],
+        })
+    }
+}
+
+pub fn test_type_affinity() -> Result<(), Error> {
+    let cases = [
+        ("INTEGER", false, "i64"),
+        ("UNSIGNED BIG INT", false, "i64"),
+        ("varchar(255)", false, "String"),
+        ("CLOB", false, "String"),
+        ("", false, "Vec<u8>"),
+        ("BLOB", false, "Vec<u8>"),
+        ("DOUBLE PRECISION", false, "f64"),
+        ("FLOATING POINT", false, "i64"),
+        ("DECIMAL(10,5)", false, "f64"),
+        ("INT", true, "i64"),
+        ("TEXT", true, "String"),
+        ("REAL", true, "f64"),
+        ("BLOB", true, "Vec<u8>"),
+        ("ANY", true, "Vec<u8>"),
+    ];
+
+    for (declared_type, is_strict, expected) in cases {
+        let actual = sqlite::type_name_from(declared_type, is_strict).to_string();
+        if actual != expected {
+            bail!("expected `{declared_type}` (strict: {is_strict}) to map to `{expected}` but got `{actual}`");
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates code from a synchronous provider on a thread without a tokio runtime, which would panic if any of the
+/// files were written asynchronously. The files are written incrementally, the way `run` would write them
+pub fn test_blocking_provider() -> Result<(), Error> {
+    let target_dir = env::temp_dir().join("autostruct_blocking_provider");
+    let _ = fs::remove_dir_all(&target_dir);
+    let args = generator::Arguments {
+        target_dir: target_dir.to_string_lossy().to_string(),
+        incremental: true,
+        ..Default::default()
+    };
+    generator::run_blocking(SqliteTypes, args).context("autostruct generator failed")?;
+
+    let generated = fs::read_to_string(target_dir.join("notes.rs"))
+        .context("failed to read the notes.rs generated for the notes table")?;
+    let expected = "pub struct Notes {\n    pub id: i64,\n    pub body: String,\n}";
+    if !generated.contains(expected) {
+        bail!("expected the generated code to contain\n{expected}\nbut got\n{generated}");
+    }
+    if !target_dir.join(generator::FINGERPRINTS_FILE).exists() {
+        bail!("expected the incremental run to store the fingerprints of the generated files");
+    }
+
+    Ok(())
+}